@@ -0,0 +1,287 @@
+use index::NetIndex;
+use index::Topology;
+use net::Net;
+use net::NetErrors;
+use node::Cost;
+use node::Point;
+use observer::NoObserver;
+use path::Path;
+use path::PathBuilder;
+use shortest_path::dijkstra;
+use std::collections::HashMap;
+
+/// Costs and next hops between every pair of points of a net, so any shortest
+/// path can be rebuilt without searching again.
+#[derive(Debug)]
+pub struct ShortestPathMatrix<T: Point> {
+    points: Vec<T>,
+    positions: HashMap<T::Identifier, usize>,
+    costs: Vec<Option<Cost>>,
+    next_hops: Vec<Option<NextHop>>,
+    /// Labels of the labeled connections, by the position of their point and their index in its connections.
+    labels: HashMap<(usize, usize), String>,
+}
+
+/// Position of the next point and the connection taken to it, none from a point to itself.
+type NextHop = (usize, Option<usize>);
+
+impl<T: Point, P> Net<T, P> {
+    /// Uses Floyd–Warshall on dense nets and a Dijkstra search from every point on sparse ones.
+    pub fn all_pairs_shortest_paths(&self) -> ShortestPathMatrix<T> {
+        all_pairs_shortest_paths(&NetIndex::new(self))
+    }
+}
+
+pub(crate) fn all_pairs_shortest_paths<T: Point, G: Topology<T>>(graph: &G) -> ShortestPathMatrix<T> {
+    let size = graph.len();
+    let log_size = (usize::BITS - size.leading_zeros()) as usize;
+
+    let (costs, next_hops) = if graph.connection_count() * log_size >= size * size {
+        floyd_warshall(graph)
+    } else {
+        repeated_dijkstra(graph)
+    };
+
+    let points: Vec<T> = (0..size)
+        .map(|position| graph.point_at(position).clone())
+        .collect();
+    let positions = points.iter()
+        .enumerate()
+        .map(|(position, point)| (point.id(), position))
+        .collect();
+    let labels = (0..size)
+        .flat_map(|position| (0..graph.connections(position).len())
+            .filter_map(move |connection| graph.label(position, connection)
+                .map(|label| ((position, connection), label.to_string()))))
+        .collect();
+
+    ShortestPathMatrix { points, positions, costs, next_hops, labels }
+}
+
+impl<T: Point> ShortestPathMatrix<T> {
+    pub fn points(&self) -> &[T] {
+        &self.points
+    }
+
+    pub fn cost(&self, origin: &T, destination: &T) -> Result<Option<Cost>, NetErrors> {
+        let cell = self.cell(origin, destination)?;
+        Ok(self.costs[cell])
+    }
+
+    pub fn next_hop(&self, origin: &T, destination: &T) -> Result<Option<&T>, NetErrors> {
+        let cell = self.cell(origin, destination)?;
+        Ok(self.next_hops[cell].map(|(position, _)| &self.points[position]))
+    }
+
+    /// Shortest path between the points, through the connections it takes when they are parallel.
+    pub fn path(&self, origin: &T, destination: &T) -> Result<Path<T>, NetErrors> {
+        let origin = self.position(origin)?;
+        let destination = self.position(destination)?;
+        if self.next_hops[origin * self.points.len() + destination].is_none() {
+            return Err(NetErrors::NoPathFound);
+        }
+
+        let mut points = vec![self.points[origin].clone()];
+        let mut labels = Vec::new();
+        let mut current = origin;
+        while current != destination {
+            let (next, connection) = self.next_hops[current * self.points.len() + destination]
+                .expect("next hops of a reachable destination lead to it");
+            let connection = connection.expect("points are only their own next hop at the destination");
+            labels.push(self.labels.get(&(current, connection)).cloned());
+            points.push(self.points[next].clone());
+            current = next;
+        }
+
+        Ok(PathBuilder::new()
+            .points(points)
+            .labels(labels)
+            .build()
+            .expect("a path from the origin always has points"))
+    }
+
+    fn cell(&self, origin: &T, destination: &T) -> Result<usize, NetErrors> {
+        Ok(self.position(origin)? * self.points.len() + self.position(destination)?)
+    }
+
+    fn position(&self, point: &T) -> Result<usize, NetErrors> {
+        self.positions.get(&point.id())
+            .cloned()
+            .ok_or_else(|| NetErrors::PointNotFound(point.id().to_string()))
+    }
+}
+
+fn floyd_warshall<T: Point, G: Topology<T>>(graph: &G) -> (Vec<Option<Cost>>, Vec<Option<NextHop>>) {
+    let size = graph.len();
+    let mut costs: Vec<Option<Cost>> = vec![None; size * size];
+    let mut next_hops: Vec<Option<NextHop>> = vec![None; size * size];
+
+    for from in 0..size {
+        costs[from * size + from] = Some(0);
+        next_hops[from * size + from] = Some((from, None));
+        for (connection, &(to, cost)) in graph.connections(from).iter().enumerate() {
+            let cell = from * size + to;
+            if costs[cell].is_none_or(|known_cost| cost < known_cost) {
+                costs[cell] = Some(cost);
+                next_hops[cell] = Some((to, Some(connection)));
+            }
+        }
+    }
+
+    for through in 0..size {
+        for from in 0..size {
+            let cost_to_through = match costs[from * size + through] {
+                Some(cost) => cost,
+                None => continue
+            };
+            for to in 0..size {
+                if let Some(cost_from_through) = costs[through * size + to] {
+                    let cost = cost_to_through + cost_from_through;
+                    let cell = from * size + to;
+                    if costs[cell].is_none_or(|known_cost| cost < known_cost) {
+                        costs[cell] = Some(cost);
+                        next_hops[cell] = next_hops[from * size + through];
+                    }
+                }
+            }
+        }
+    }
+
+    (costs, next_hops)
+}
+
+fn repeated_dijkstra<T: Point, G: Topology<T>>(graph: &G) -> (Vec<Option<Cost>>, Vec<Option<NextHop>>) {
+    let size = graph.len();
+    let mut costs: Vec<Option<Cost>> = Vec::with_capacity(size * size);
+    let mut next_hops: Vec<Option<NextHop>> = Vec::with_capacity(size * size);
+
+    for origin in 0..size {
+        let tree = dijkstra(graph, &[origin], |_| false, &mut NoObserver);
+
+        // Points are settled after their predecessor, so its next hop is always known
+        let mut origin_next_hops: Vec<Option<NextHop>> = vec![None; size];
+        for position in tree.settled {
+            origin_next_hops[position] = match tree.previous[position] {
+                None => Some((position, None)),
+                Some(previous) if previous == origin => Some((position, Some(tree.previous_connection[position]))),
+                Some(previous) => origin_next_hops[previous]
+            };
+        }
+
+        costs.extend(tree.costs);
+        next_hops.extend(origin_next_hops);
+    }
+
+    (costs, next_hops)
+}
+
+#[cfg(test)]
+mod test {
+    use all_pairs::*;
+    use node::Node;
+    use node::NodeBuilder;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this net of points:
+    // A -1- B -1- C
+    //  \         /
+    //   5       1
+    //    \     /
+    //       D     E
+    fn weighted_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', vec![('B', 1), ('D', 5)]),
+                node('B', vec![('A', 1), ('C', 1)]),
+                node('C', vec![('B', 1), ('D', 1)]),
+                node('D', vec![('A', 5), ('C', 1)]),
+                node('E', vec![]),
+            ]
+        }
+    }
+
+    #[test]
+    fn floyd_warshall_and_repeated_dijkstra_should_agree_on_costs() {
+        let net = weighted_net();
+        let index = NetIndex::new(&net);
+
+        let (floyd_warshall_costs, _) = floyd_warshall(&index);
+        let (dijkstra_costs, _) = repeated_dijkstra(&index);
+
+        assert_eq!(floyd_warshall_costs, dijkstra_costs);
+    }
+
+    #[test]
+    fn matrix_should_rebuild_shortest_paths() {
+        let net = weighted_net();
+
+        let matrix = net.all_pairs_shortest_paths();
+
+        let path = matrix.path(&simple_point('A'), &simple_point('D')).expect("should rebuild A to D");
+        assert_eq!(format!("{}", path), "A-B-C-D");
+        assert_eq!(matrix.cost(&simple_point('D'), &simple_point('A')).unwrap(), Some(3));
+        assert_eq!(matrix.next_hop(&simple_point('D'), &simple_point('A')).unwrap(), Some(&simple_point('C')));
+    }
+
+    #[test]
+    fn matrix_should_throw_if_points_are_not_connected() {
+        let net = weighted_net();
+
+        let matrix = net.all_pairs_shortest_paths();
+
+        assert_eq!(matrix.cost(&simple_point('A'), &simple_point('E')).unwrap(), None);
+        match matrix.path(&simple_point('A'), &simple_point('E')) {
+            Err(NetErrors::NoPathFound) => {},
+            other => panic!("NoPathFound expected, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn matrix_should_keep_the_parallel_connection_each_path_takes() {
+        let (a, b, c) = (simple_point('A'), simple_point('B'), simple_point('C'));
+        let net = Net {
+            nodes: vec![
+                NodeBuilder::new()
+                    .point(&a)
+                    .connected_point_with_label(&b, "bus", 5)
+                    .connected_point_with_label(&b, "train", 2)
+                    .build()
+                    .unwrap(),
+                NodeBuilder::new().point(&b).connected_point_with_label(&c, "walk", 1).build().unwrap(),
+                node('C', vec![]),
+            ]
+        };
+        let index = NetIndex::new(&net);
+
+        let path = net.all_pairs_shortest_paths().path(&a, &c).expect("should rebuild A to C");
+
+        assert_eq!(path.labels(), &[Some(String::from("train")), Some(String::from("walk"))]);
+        assert_eq!(floyd_warshall(&index).1, repeated_dijkstra(&index).1, "both should take the train");
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connections: Vec<(char, Cost)>) -> Node<SimplePoint> {
+        let mut builder = NodeBuilder::new();
+        builder.point(&simple_point(name));
+        connections.into_iter()
+            .for_each(|(to, cost)| {
+                builder.connected_point_with_cost(&simple_point(to), cost);
+            });
+        builder.build().unwrap()
+    }
+}
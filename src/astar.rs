@@ -0,0 +1,160 @@
+use compact::CompactNet;
+use index::NetIndex;
+use index::Topology;
+use net::Net;
+use net::NetErrors;
+use node::Cost;
+use node::Point;
+use path::Path;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+impl<T: Point, P> Net<T, P> {
+    /// Finds the cheapest path exploring first the points `heuristic` estimates closer to the
+    /// destination. The heuristic must never estimate more than the actual cost to the destination.
+    pub fn astar_shortest_path<H>(&self, origin: &T, destination: &T, heuristic: H) -> Result<Path<T>, NetErrors>
+        where H: Fn(&T) -> Cost {
+        astar_shortest_path(&NetIndex::new(self), origin, destination, heuristic)
+    }
+}
+
+impl<T: Point, P> CompactNet<T, P> {
+    pub fn astar_shortest_path<H>(&self, origin: &T, destination: &T, heuristic: H) -> Result<Path<T>, NetErrors>
+        where H: Fn(&T) -> Cost {
+        astar_shortest_path(self, origin, destination, heuristic)
+    }
+}
+
+/// Points are explored again when reached cheaper, so an admissible heuristic that is
+/// not consistent still finds the cheapest path.
+fn astar_shortest_path<T, G, H>(graph: &G, origin: &T, destination: &T, heuristic: H) -> Result<Path<T>, NetErrors>
+    where T: Point, G: Topology<T>, H: Fn(&T) -> Cost {
+    let origin = graph.position(origin)?;
+    let destination = graph.position(destination)?;
+
+    let mut costs: Vec<Option<Cost>> = vec![None; graph.len()];
+    let mut previous: Vec<Option<usize>> = vec![None; graph.len()];
+    let mut pending = BinaryHeap::new();
+    costs[origin] = Some(0);
+    pending.push(Reverse((heuristic(graph.point_at(origin)), 0, origin)));
+
+    while let Some(Reverse((_, cost, position))) = pending.pop() {
+        if costs[position].is_some_and(|known_cost| cost > known_cost) {
+            continue;
+        }
+
+        if position == destination {
+            let mut positions = vec![position];
+            while let Some(previous_position) = previous[*positions.last().unwrap()] {
+                positions.push(previous_position);
+            }
+            positions.reverse();
+            return Ok(graph.path(&positions));
+        }
+
+        for &(next, connection_cost) in graph.connections(position) {
+            let next_cost = cost + connection_cost;
+            if costs[next].is_none_or(|known_cost| next_cost < known_cost) {
+                costs[next] = Some(next_cost);
+                previous[next] = Some(position);
+                pending.push(Reverse((next_cost + heuristic(graph.point_at(next)), next_cost, next)));
+            }
+        }
+    }
+
+    Err(NetErrors::NoPathFound)
+}
+
+#[cfg(test)]
+mod test {
+    use astar::*;
+    use node::Node;
+    use node::NodeBuilder;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this net of points:
+    // A -1- B -1- C
+    //  \         /
+    //   5       1
+    //    \     /
+    //       D -4- E
+    fn weighted_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', vec![('B', 1), ('D', 5)]),
+                node('B', vec![('A', 1), ('C', 1)]),
+                node('C', vec![('B', 1), ('D', 1)]),
+                node('D', vec![('A', 5), ('C', 1), ('E', 4)]),
+                node('E', vec![('D', 4)]),
+            ]
+        }
+    }
+
+    // Cost of the cheapest path from each point to E, never more than the actual cost
+    fn estimate_to_e(point: &SimplePoint) -> Cost {
+        match point.name {
+            'A' => 7,
+            'B' => 6,
+            'C' => 5,
+            'D' => 4,
+            _ => 0
+        }
+    }
+
+    #[test]
+    fn astar_should_find_the_cheapest_path() {
+        let net = weighted_net();
+
+        let path = net.astar_shortest_path(&simple_point('A'), &simple_point('E'), estimate_to_e)
+            .expect("should find a path from A to E");
+
+        assert_eq!(format!("{}", path), "A-B-C-D-E");
+    }
+
+    #[test]
+    fn astar_without_heuristic_should_find_the_same_path_as_dijkstra() {
+        let net = weighted_net().compact();
+        let (a, e) = (simple_point('A'), simple_point('E'));
+
+        let path = net.astar_shortest_path(&a, &e, |_| 0).expect("should find a path from A to E");
+
+        assert_eq!(format!("{}", path), format!("{}", net.shortest_path(&a, &e).unwrap()));
+    }
+
+    #[test]
+    fn astar_should_throw_if_there_is_no_path() {
+        let mut net = weighted_net();
+        net.nodes[3] = node('D', vec![('A', 5), ('C', 1)]);
+
+        match net.astar_shortest_path(&simple_point('A'), &simple_point('E'), estimate_to_e) {
+            Err(NetErrors::NoPathFound) => {},
+            other => panic!("NoPathFound expected, got {:?}", other)
+        }
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connections: Vec<(char, Cost)>) -> Node<SimplePoint> {
+        let mut builder = NodeBuilder::new();
+        builder.point(&simple_point(name));
+        connections.into_iter()
+            .for_each(|(to, cost)| {
+                builder.connected_point_with_cost(&simple_point(to), cost);
+            });
+        builder.build().unwrap()
+    }
+}
@@ -0,0 +1,332 @@
+use index::Adjacency;
+use index::NetIndex;
+use index::Topology;
+use net::Net;
+use net::NetErrors;
+use node::Cost;
+use node::Point;
+use observer::NoObserver;
+use observer::SearchControl;
+use observer::SearchObserver;
+use path::Path;
+use shortest_path;
+use shortest_path::Distance;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+impl<T: Point, P> Net<T, P> {
+    /// Finds the same cheapest path as `shortest_path`, exploring from the origin
+    /// and, following connections backwards, from the destination at the same time.
+    /// Paths that cost the same are told apart the same way too.
+    pub fn bidirectional_shortest_path(&self, origin: &T, destination: &T) -> Result<Path<T>, NetErrors> {
+        self.bidirectional_shortest_path_observed(origin, destination, &mut NoObserver)
+    }
+
+    /// Points expanded following connections backwards arrive through the connection they are left by.
+    pub fn bidirectional_shortest_path_observed<O>(&self, origin: &T, destination: &T, observer: &mut O) -> Result<Path<T>, NetErrors>
+        where O: SearchObserver<T, P> + ?Sized {
+        bidirectional_shortest_path(&NetIndex::new(self), origin, destination, observer)
+    }
+}
+
+pub(crate) fn bidirectional_shortest_path<T, G, O>(graph: &G, origin: &T, destination: &T, observer: &mut O) -> Result<Path<T>, NetErrors>
+    where T: Point, G: Topology<T>, O: SearchObserver<T, G::Payload> + ?Sized {
+    let origin = graph.position(origin)?;
+    let destination = graph.position(destination)?;
+    if origin == destination {
+        return Err(NetErrors::NoPathFound);
+    }
+    let reversed = Adjacency::reversed(graph);
+
+    let mut forward = Frontier::new(graph.len(), origin);
+    let mut backward = Frontier::new(graph.len(), destination);
+    let mut best: Option<Distance> = None;
+
+    // Points costing exactly as much as the best path so far are settled too, so
+    // every point of the cheapest paths is settled by one of the sides.
+    while let (Some(forward_distance), Some(backward_distance)) = (forward.next_distance(), backward.next_distance()) {
+        if best.is_some_and(|best| (forward_distance.0 + backward_distance.0, forward_distance.1 + backward_distance.1) > best) {
+            break;
+        }
+
+        let control = if forward_distance <= backward_distance {
+            forward.settle_next(graph, |position| graph.connections(position), |position, connection| graph.payload(position, connection),
+                                &backward, &mut best, observer)
+        } else {
+            let arriving_payload = |position: usize, connection: usize| {
+                let (from, _) = reversed.connections(position)[connection];
+                graph.payload(from, reversed.source(position, connection))
+            };
+            backward.settle_next(graph, |position| reversed.connections(position), arriving_payload, &forward, &mut best, observer)
+        };
+        if control == SearchControl::Stop {
+            return Err(NetErrors::Stopped);
+        }
+    }
+
+    match best {
+        None => Err(NetErrors::NoPathFound),
+        Some(best) => {
+            let path = shortest_path::cheapest_path(graph, &[origin], destination, &forward.settled_distances(),
+                                                    &forward.settled_order, &backward.settled_distances(), best);
+            observer.on_path_found(&path);
+            Ok(path)
+        }
+    }
+}
+
+struct Frontier {
+    costs: Vec<Option<Cost>>,
+    previous: Vec<Option<usize>>,
+    /// Index of the connection taken from the previous position, among the connections the frontier follows.
+    previous_connection: Vec<usize>,
+    hops: Vec<usize>,
+    settled: Vec<bool>,
+    settled_order: Vec<usize>,
+    pending: BinaryHeap<Reverse<(Distance, usize)>>,
+}
+
+impl Frontier {
+    fn new(size: usize, start: usize) -> Frontier {
+        let mut costs = vec![None; size];
+        costs[start] = Some(0);
+        let mut pending = BinaryHeap::new();
+        pending.push(Reverse(((0, 0), start)));
+
+        Frontier {
+            costs, previous: vec![None; size], previous_connection: vec![0; size], hops: vec![0; size],
+            settled: vec![false; size], settled_order: Vec::new(), pending
+        }
+    }
+
+    fn next_distance(&mut self) -> Option<Distance> {
+        while let Some(&Reverse((distance, position))) = self.pending.peek() {
+            if !self.settled[position] {
+                return Some(distance);
+            }
+            self.pending.pop();
+        }
+
+        None
+    }
+
+    /// Settles the next pending position following `connections`, where `payload` gives
+    /// the payload of each of them by the position it leaves and its index.
+    fn settle_next<'c, T, G, F, L, O>(&mut self, graph: &G, connections: F, payload: L, other_side: &Frontier,
+                                      best: &mut Option<Distance>, observer: &mut O) -> SearchControl
+        where T: Point, G: Topology<T>, G::Payload: 'c, F: Fn(usize) -> &'c [(usize, Cost)],
+              L: Fn(usize, usize) -> &'c G::Payload, O: SearchObserver<T, G::Payload> + ?Sized {
+        let Reverse(((cost, hops), position)) = match self.pending.pop() {
+            Some(next) => next,
+            None => return SearchControl::Continue
+        };
+        self.settled[position] = true;
+        self.settled_order.push(position);
+
+        let arrived_through = self.previous[position]
+            .map(|previous_position| payload(previous_position, self.previous_connection[position]));
+        if observer.on_expand(graph.point_at(position), hops, arrived_through) == SearchControl::Stop {
+            return SearchControl::Stop;
+        }
+
+        for (connection, &(next, connection_cost)) in connections(position).iter().enumerate() {
+            if self.settled[next] {
+                if observer.on_prune(graph.point_at(position), graph.point_at(next), payload(position, connection)) == SearchControl::Stop {
+                    return SearchControl::Stop;
+                }
+                continue;
+            }
+
+            let next_distance = (cost + connection_cost, hops + 1);
+            if self.costs[next].is_none_or(|known_cost| next_distance < (known_cost, self.hops[next])) {
+                self.costs[next] = Some(next_distance.0);
+                self.previous[next] = Some(position);
+                self.previous_connection[next] = connection;
+                self.hops[next] = next_distance.1;
+                self.pending.push(Reverse((next_distance, next)));
+            }
+
+            if let (Some(cost_here), Some(cost_there)) = (self.costs[next], other_side.costs[next]) {
+                let through = (cost_here + cost_there, self.hops[next] + other_side.hops[next]);
+                if best.is_none_or(|best| through < best) {
+                    *best = Some(through);
+                }
+            }
+        }
+
+        SearchControl::Continue
+    }
+
+    /// Distances of the settled positions from the start of the frontier.
+    fn settled_distances(&self) -> Vec<Option<Distance>> {
+        (0..self.costs.len())
+            .map(|position| self.costs[position]
+                .filter(|_| self.settled[position])
+                .map(|cost| (cost, self.hops[position])))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bidirectional::*;
+    use node::Cost;
+    use node::Node;
+    use node::NodeBuilder;
+    use node::Point;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this directed net of points:
+    // A -1-> B -1-> C -1-> D
+    // |             ^      |
+    // 2             1      1
+    // v             |      v
+    // E ----5-----> F      G
+    fn directed_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', vec![('B', 1), ('E', 2)]),
+                node('B', vec![('C', 1)]),
+                node('C', vec![('D', 1)]),
+                node('D', vec![('G', 1)]),
+                node('E', vec![('F', 5)]),
+                node('F', vec![('C', 1)]),
+                node('G', vec![]),
+            ]
+        }
+    }
+
+    #[test]
+    fn bidirectional_search_should_find_the_same_path_as_the_one_directional_search() {
+        let net = directed_net();
+
+        for (origin, destination) in [('A', 'G'), ('E', 'D'), ('A', 'C')] {
+            let bidirectional = net.bidirectional_shortest_path(&simple_point(origin), &simple_point(destination))
+                .expect("should find a bidirectional path");
+            let one_directional = net.shortest_path(&simple_point(origin), &simple_point(destination))
+                .expect("should find a one directional path");
+
+            assert_eq!(format!("{}", bidirectional), format!("{}", one_directional));
+        }
+        assert!(net.bidirectional_shortest_path(&simple_point('B'), &simple_point('B')).is_err(), "no path goes from B to itself");
+    }
+
+    // Given this directed net of points, with the connections of A added in reverse order:
+    //  /---------4---------v
+    // A -1-> B -2-> D -1-> E
+    //  \-1-> C -2---^
+    #[test]
+    fn searches_should_break_ties_between_paths_that_cost_the_same_the_same_way() {
+        let net = Net {
+            nodes: vec![
+                node('A', vec![('E', 4), ('C', 1), ('B', 1)]),
+                node('B', vec![('D', 2)]),
+                node('C', vec![('D', 2)]),
+                node('D', vec![('E', 1)]),
+                node('E', vec![]),
+            ]
+        };
+
+        for (destination, expected) in [('D', "A-B-D"), ('E', "A-E")] {
+            let bidirectional = net.bidirectional_shortest_path(&simple_point('A'), &simple_point(destination))
+                .expect("should find a bidirectional path");
+            let one_directional = net.shortest_path(&simple_point('A'), &simple_point(destination))
+                .expect("should find a one directional path");
+
+            assert_eq!(format!("{}", bidirectional), expected, "fewest hops first, and then B as it was added before C");
+            assert_eq!(format!("{}", one_directional), expected);
+        }
+    }
+
+    #[test]
+    fn bidirectional_search_should_follow_connection_direction() {
+        let net = directed_net();
+
+        match net.bidirectional_shortest_path(&simple_point('G'), &simple_point('A')) {
+            Err(NetErrors::NoPathFound) => {},
+            other => panic!("NoPathFound expected, got {:?}", other)
+        }
+    }
+
+    struct StopOnExpand;
+
+    impl SearchObserver<SimplePoint> for StopOnExpand {
+        fn on_expand(&mut self, _point: &SimplePoint, _depth: usize, _arrived_through: Option<&()>) -> SearchControl {
+            SearchControl::Stop
+        }
+    }
+
+    #[test]
+    fn bidirectional_search_stopped_by_its_observer_should_not_look_like_a_missing_path() {
+        let net = directed_net();
+
+        match net.bidirectional_shortest_path_observed(&simple_point('A'), &simple_point('G'), &mut StopOnExpand) {
+            Err(NetErrors::Stopped) => {},
+            other => panic!("Stopped expected, got {:?}", other)
+        }
+    }
+
+    struct RecordExpansions {
+        expansions: Vec<String>,
+    }
+
+    impl SearchObserver<SimplePoint, &'static str> for RecordExpansions {
+        fn on_expand(&mut self, point: &SimplePoint, _depth: usize, arrived_through: Option<&&'static str>) -> SearchControl {
+            self.expansions.push(format!("{} through {}", point.name, arrived_through.unwrap_or(&"nothing")));
+            SearchControl::Continue
+        }
+    }
+
+    // Given this directed net of points, with the road of each connection:
+    // A -ab-> B -bc-> C -cd-> D -de-> E
+    #[test]
+    fn backward_expansions_should_see_the_payload_of_the_connection_they_walk_backwards() {
+        let names = ['A', 'B', 'C', 'D', 'E'];
+        let roads = ["ab", "bc", "cd", "de"];
+        let net: Net<SimplePoint, &str> = Net {
+            nodes: names.iter()
+                .enumerate()
+                .map(|(index, &name)| {
+                    let mut builder = NodeBuilder::default();
+                    builder.point(&simple_point(name));
+                    if let Some(&to) = names.get(index + 1) {
+                        builder.connected_point_with_payload(&simple_point(to), 1, roads[index]);
+                    }
+                    builder.build().unwrap()
+                })
+                .collect()
+        };
+        let mut observer = RecordExpansions { expansions: Vec::new() };
+
+        net.bidirectional_shortest_path_observed(&simple_point('A'), &simple_point('E'), &mut observer)
+            .expect("should find a path from A to E");
+
+        assert_eq!(observer.expansions, vec!["A through nothing", "E through nothing", "B through ab", "D through de", "C through bc"]);
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connections: Vec<(char, Cost)>) -> Node<SimplePoint> {
+        let mut builder = NodeBuilder::new();
+        builder.point(&simple_point(name));
+        connections.into_iter()
+            .for_each(|(to, cost)| {
+                builder.connected_point_with_cost(&simple_point(to), cost);
+            });
+        builder.build().unwrap()
+    }
+}
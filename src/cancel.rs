@@ -0,0 +1,319 @@
+use bidirectional;
+use compact::CompactNet;
+use index::NetIndex;
+use index::Topology;
+use net;
+use net::Net;
+use net::NetErrors;
+use node::Point;
+use observer::SearchControl;
+use observer::SearchObserver;
+use path::Path;
+use shortest_path;
+use std::error::Error;
+use std::fmt;
+use std::slice;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Handle to abort a running search from another thread, or once a deadline passes.
+/// Clones share the same flag, so any of them can cancel the search.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    pub fn with_deadline(deadline: Instant) -> CancellationToken {
+        CancellationToken { cancelled: Arc::new(AtomicBool::new(false)), deadline: Some(deadline) }
+    }
+
+    pub fn with_timeout(timeout: Duration) -> CancellationToken {
+        CancellationToken::with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+impl From<Arc<AtomicBool>> for CancellationToken {
+    fn from(cancelled: Arc<AtomicBool>) -> CancellationToken {
+        CancellationToken { cancelled, deadline: None }
+    }
+}
+
+/// Stops the search it watches as soon as the token is cancelled.
+struct Cancellation<'a> {
+    token: &'a CancellationToken,
+    fired: bool,
+}
+
+impl<'a> Cancellation<'a> {
+    fn new(token: &'a CancellationToken) -> Cancellation<'a> {
+        Cancellation { token, fired: false }
+    }
+
+    fn check(&mut self) -> SearchControl {
+        if self.token.is_cancelled() {
+            self.fired = true;
+            SearchControl::Stop
+        } else {
+            SearchControl::Continue
+        }
+    }
+}
+
+impl<'a, T: Point, P> SearchObserver<T, P> for Cancellation<'a> {
+    fn on_expand(&mut self, _point: &T, _depth: usize, _arrived_through: Option<&P>) -> SearchControl {
+        self.check()
+    }
+
+    fn on_prune(&mut self, _from: &T, _to: &T, _payload: &P) -> SearchControl {
+        self.check()
+    }
+}
+
+/// Errors of the cancellable searches, which fail with the paths found until the token
+/// was cancelled, as callers could miss a flag on a successful result.
+#[derive(Debug)]
+pub enum CancellableErrors<T: Point> {
+    Cancelled { partial: Vec<Path<T>> },
+    Failed(NetErrors),
+}
+
+impl<T: Point> From<NetErrors> for CancellableErrors<T> {
+    fn from(error: NetErrors) -> CancellableErrors<T> {
+        CancellableErrors::Failed(error)
+    }
+}
+
+impl<T: Point> fmt::Display for CancellableErrors<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CancellableErrors::Cancelled { ref partial } => write!(f, "Search was cancelled after finding {} paths", partial.len()),
+            CancellableErrors::Failed(ref error) => write!(f, "{}", error)
+        }
+    }
+}
+
+impl<T: Point + fmt::Debug> Error for CancellableErrors<T> {}
+
+impl<T: Point, P> Net<T, P> {
+    /// Stops as soon as the token is cancelled, failing with the paths found until then.
+    pub fn find_paths_cancellable(&self, origin: &T, destination: &T, token: &CancellationToken) -> Result<Vec<Path<T>>, CancellableErrors<T>> {
+        find_paths_cancellable(&NetIndex::new(self), slice::from_ref(origin), slice::from_ref(destination), token)
+    }
+
+    pub fn find_paths_between_sets_cancellable(&self, origins: &[T], destinations: &[T], token: &CancellationToken) -> Result<Vec<Path<T>>, CancellableErrors<T>> {
+        find_paths_cancellable(&NetIndex::new(self), origins, destinations, token)
+    }
+
+    /// Fails with `CancellableErrors::Cancelled` if the token is cancelled before the path is found.
+    pub fn shortest_path_cancellable(&self, origin: &T, destination: &T, token: &CancellationToken) -> Result<Path<T>, CancellableErrors<T>> {
+        shortest_path_cancellable(&NetIndex::new(self), slice::from_ref(origin), slice::from_ref(destination), token)
+    }
+
+    pub fn shortest_path_between_sets_cancellable(&self, origins: &[T], destinations: &[T], token: &CancellationToken) -> Result<Path<T>, CancellableErrors<T>> {
+        shortest_path_cancellable(&NetIndex::new(self), origins, destinations, token)
+    }
+
+    pub fn shortest_paths_per_pair_cancellable(&self, origins: &[T], destinations: &[T], token: &CancellationToken) -> Result<Vec<Path<T>>, CancellableErrors<T>> {
+        shortest_paths_per_pair_cancellable(&NetIndex::new(self), origins, destinations, token)
+    }
+
+    pub fn bidirectional_shortest_path_cancellable(&self, origin: &T, destination: &T, token: &CancellationToken) -> Result<Path<T>, CancellableErrors<T>> {
+        bidirectional_shortest_path_cancellable(&NetIndex::new(self), origin, destination, token)
+    }
+}
+
+impl<T: Point, P> CompactNet<T, P> {
+    pub fn find_paths_cancellable(&self, origin: &T, destination: &T, token: &CancellationToken) -> Result<Vec<Path<T>>, CancellableErrors<T>> {
+        find_paths_cancellable(self, slice::from_ref(origin), slice::from_ref(destination), token)
+    }
+
+    pub fn find_paths_between_sets_cancellable(&self, origins: &[T], destinations: &[T], token: &CancellationToken) -> Result<Vec<Path<T>>, CancellableErrors<T>> {
+        find_paths_cancellable(self, origins, destinations, token)
+    }
+
+    pub fn shortest_path_cancellable(&self, origin: &T, destination: &T, token: &CancellationToken) -> Result<Path<T>, CancellableErrors<T>> {
+        shortest_path_cancellable(self, slice::from_ref(origin), slice::from_ref(destination), token)
+    }
+
+    pub fn shortest_path_between_sets_cancellable(&self, origins: &[T], destinations: &[T], token: &CancellationToken) -> Result<Path<T>, CancellableErrors<T>> {
+        shortest_path_cancellable(self, origins, destinations, token)
+    }
+
+    pub fn shortest_paths_per_pair_cancellable(&self, origins: &[T], destinations: &[T], token: &CancellationToken) -> Result<Vec<Path<T>>, CancellableErrors<T>> {
+        shortest_paths_per_pair_cancellable(self, origins, destinations, token)
+    }
+
+    pub fn bidirectional_shortest_path_cancellable(&self, origin: &T, destination: &T, token: &CancellationToken) -> Result<Path<T>, CancellableErrors<T>> {
+        bidirectional_shortest_path_cancellable(self, origin, destination, token)
+    }
+}
+
+fn find_paths_cancellable<T, G>(graph: &G, origins: &[T], destinations: &[T], token: &CancellationToken) -> Result<Vec<Path<T>>, CancellableErrors<T>>
+    where T: Point, G: Topology<T> {
+    let mut cancellation = Cancellation::new(token);
+    let paths = net::find_paths_between_sets(graph, origins, destinations, &mut cancellation);
+    paths_or_cancelled(paths, cancellation.fired)
+}
+
+fn shortest_path_cancellable<T, G>(graph: &G, origins: &[T], destinations: &[T], token: &CancellationToken) -> Result<Path<T>, CancellableErrors<T>>
+    where T: Point, G: Topology<T> {
+    let mut cancellation = Cancellation::new(token);
+    let path = shortest_path::shortest_path_between_sets(graph, origins, destinations, &mut cancellation);
+    path_or_cancelled(path, cancellation.fired)
+}
+
+fn shortest_paths_per_pair_cancellable<T, G>(graph: &G, origins: &[T], destinations: &[T], token: &CancellationToken) -> Result<Vec<Path<T>>, CancellableErrors<T>>
+    where T: Point, G: Topology<T> {
+    let mut cancellation = Cancellation::new(token);
+    let paths = shortest_path::shortest_paths_per_pair(graph, origins, destinations, &mut cancellation);
+    paths_or_cancelled(paths, cancellation.fired)
+}
+
+fn bidirectional_shortest_path_cancellable<T, G>(graph: &G, origin: &T, destination: &T, token: &CancellationToken) -> Result<Path<T>, CancellableErrors<T>>
+    where T: Point, G: Topology<T> {
+    let mut cancellation = Cancellation::new(token);
+    let path = bidirectional::bidirectional_shortest_path(graph, origin, destination, &mut cancellation);
+    path_or_cancelled(path, cancellation.fired)
+}
+
+fn paths_or_cancelled<T: Point>(paths: Result<Vec<Path<T>>, NetErrors>, cancelled: bool) -> Result<Vec<Path<T>>, CancellableErrors<T>> {
+    match paths {
+        Ok(paths) if cancelled => Err(CancellableErrors::Cancelled { partial: paths }),
+        Err(NetErrors::NoPathFound) | Err(NetErrors::Stopped) if cancelled => Err(CancellableErrors::Cancelled { partial: Vec::new() }),
+        paths => Ok(paths?)
+    }
+}
+
+fn path_or_cancelled<T: Point>(path: Result<Path<T>, NetErrors>, cancelled: bool) -> Result<Path<T>, CancellableErrors<T>> {
+    match path {
+        Err(NetErrors::Stopped) if cancelled => Err(CancellableErrors::Cancelled { partial: Vec::new() }),
+        path => Ok(path?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cancel::*;
+    use node::Node;
+    use node::NodeBuilder;
+    use std::thread;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this net of points:
+    // A - B - C
+    //  \  |  /
+    //   \ | /
+    //     D
+    fn diamond_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', "BD"),
+                node('B', "ACD"),
+                node('C', "BD"),
+                node('D', "ACB"),
+            ]
+        }
+    }
+
+    #[test]
+    fn cancelled_search_should_fail_with_the_paths_found_so_far() {
+        let names = "ABCDEFGHIJKL";
+        let complete_net = Net {
+            nodes: names.chars()
+                .map(|name| node(name, &names.replace(name, "")))
+                .collect()
+        };
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        // Every point connected to every other one has millions of paths, so the search is still running
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            canceller.cancel();
+        });
+
+        match complete_net.find_paths_cancellable(&simple_point('A'), &simple_point('L'), &token) {
+            Err(CancellableErrors::Cancelled { partial }) => {
+                assert!(!partial.is_empty(), "the first paths are found before the cancellation");
+                assert!(partial.iter().all(|path| path.ends_with(&simple_point('L'))));
+            },
+            other => panic!("Cancelled expected, got {} paths", other.map(|paths| paths.len()).unwrap_or(0))
+        }
+    }
+
+    #[test]
+    fn search_should_not_start_with_a_cancelled_token() {
+        let net = diamond_net();
+        let token = CancellationToken::new();
+        token.cancel();
+
+        match net.shortest_path_cancellable(&simple_point('A'), &simple_point('C'), &token) {
+            Err(CancellableErrors::Cancelled { ref partial }) if partial.is_empty() => {},
+            other => panic!("Cancelled expected, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn search_should_be_cancelled_once_the_deadline_passes() {
+        let net = diamond_net();
+        let token = CancellationToken::with_deadline(Instant::now());
+
+        match net.find_paths_cancellable(&simple_point('A'), &simple_point('C'), &token) {
+            Err(CancellableErrors::Cancelled { ref partial }) if partial.is_empty() => {},
+            other => panic!("Cancelled expected, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn search_should_finish_if_the_token_is_not_cancelled() {
+        let net = diamond_net();
+        let token = CancellationToken::from(Arc::new(AtomicBool::new(false)));
+
+        let paths = net.find_paths_cancellable(&simple_point('A'), &simple_point('C'), &token)
+            .expect("should find paths from A to C");
+
+        assert_eq!(paths.len(), 4);
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connected_to: &str) -> Node<SimplePoint> {
+        let connected_points: Vec<SimplePoint> = connected_to.chars().map(simple_point).collect();
+        NodeBuilder::new()
+            .point(&simple_point(name))
+            .connected_points(&connected_points)
+            .build()
+            .unwrap()
+    }
+}
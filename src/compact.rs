@@ -0,0 +1,337 @@
+use all_pairs;
+use all_pairs::ShortestPathMatrix;
+use bidirectional;
+use index;
+use index::Adjacency;
+use index::Topology;
+use net;
+use net::Net;
+use net::NetErrors;
+use node::Cost;
+use node::SideTable;
+use node::Point;
+use observer::NoObserver;
+use observer::SearchObserver;
+use path::Path;
+use shortest_path;
+use std::collections::HashMap;
+use std::slice;
+use timetable::Timetable;
+
+/// Immutable copy of a net where each point is stored once and connections are
+/// kept by position in a single contiguous array. Searches on a compact net do
+/// not need to index the net again.
+/// Labels, timetables and resources are kept by the index of their connection among
+/// the connections of every point.
+#[derive(Debug)]
+pub struct CompactNet<T: Point, P = ()> {
+    points: Vec<T>,
+    positions: HashMap<T::Identifier, usize>,
+    adjacency: Adjacency,
+    labels: SideTable<String>,
+    timetables: SideTable<Timetable>,
+    resources: SideTable<Vec<Cost>>,
+    payloads: Vec<P>,
+}
+
+impl<T: Point, P: Clone> Net<T, P> {
+    pub fn compact(&self) -> CompactNet<T, P> {
+        CompactNet::from(self)
+    }
+}
+
+impl<'a, T: Point, P: Clone> From<&'a Net<T, P>> for CompactNet<T, P> {
+    fn from(net: &'a Net<T, P>) -> CompactNet<T, P> {
+        let points = net.nodes.iter()
+            .map(|node| node.point().clone())
+            .collect();
+        let positions = index::positions_of(net);
+        let adjacency = Adjacency::new(net, &positions);
+
+        let mut labels = SideTable::default();
+        let mut timetables = SideTable::default();
+        let mut resources = SideTable::default();
+        let mut payloads = Vec::new();
+        for (position, node) in net.nodes.iter().enumerate() {
+            for connection in 0..adjacency.connections(position).len() {
+                let (source, entry) = (adjacency.source(position, connection), adjacency.entry(position, connection));
+                if let Some(label) = node.label(source) {
+                    labels.insert(entry, label.to_string());
+                }
+                if let Some(timetable) = node.timetable(source) {
+                    timetables.insert(entry, timetable.clone());
+                }
+                if !node.resources(source).is_empty() {
+                    resources.insert(entry, node.resources(source).to_vec());
+                }
+                payloads.push(node.payload(source).clone());
+            }
+        }
+
+        CompactNet { points, positions, adjacency, labels, timetables, resources, payloads }
+    }
+}
+
+impl<T: Point, P> CompactNet<T, P> {
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn contains(&self, id: &T::Identifier) -> bool {
+        self.positions.contains_key(id)
+    }
+
+    pub fn point(&self, id: &T::Identifier) -> Option<&T> {
+        self.position_of(id).map(|position| &self.points[position])
+    }
+
+    /// Payload of the cheapest connection between the points.
+    pub fn payload_between(&self, from: &T, to: &T) -> Option<&P> {
+        let from = self.position_of(&from.id())?;
+        let to = self.position_of(&to.id())?;
+        self.cheapest_connection(from, to)
+            .map(|connection| self.payload(from, connection))
+    }
+
+    pub fn neighbors(&self, id: &T::Identifier) -> Result<Vec<&T>, NetErrors> {
+        let position = self.position_or_throws(id)?;
+        Ok(self.connections(position).iter()
+            .map(|&(connected_to, _)| &self.points[connected_to])
+            .collect())
+    }
+
+    pub fn find_paths_by_id(&self, origin: &T::Identifier, destination: &T::Identifier) -> Result<Vec<Path<T>>, NetErrors> {
+        let origin = &self.points[self.position_or_throws(origin)?];
+        let destination = &self.points[self.position_or_throws(destination)?];
+        self.find_paths(origin, destination)
+    }
+
+    pub fn shortest_path_by_id(&self, origin: &T::Identifier, destination: &T::Identifier) -> Result<Path<T>, NetErrors> {
+        let origin = &self.points[self.position_or_throws(origin)?];
+        let destination = &self.points[self.position_or_throws(destination)?];
+        self.shortest_path(origin, destination)
+    }
+
+    pub fn find_paths(&self, origin: &T, destination: &T) -> Result<Vec<Path<T>>, NetErrors> {
+        self.find_paths_observed(origin, destination, &mut NoObserver)
+    }
+
+    pub fn find_paths_observed<O>(&self, origin: &T, destination: &T, observer: &mut O) -> Result<Vec<Path<T>>, NetErrors>
+        where O: SearchObserver<T, P> + ?Sized {
+        net::find_paths_between_sets(self, slice::from_ref(origin), slice::from_ref(destination), observer)
+    }
+
+    pub fn find_paths_between_sets(&self, origins: &[T], destinations: &[T]) -> Result<Vec<Path<T>>, NetErrors> {
+        self.find_paths_between_sets_observed(origins, destinations, &mut NoObserver)
+    }
+
+    pub fn find_paths_between_sets_observed<O>(&self, origins: &[T], destinations: &[T], observer: &mut O) -> Result<Vec<Path<T>>, NetErrors>
+        where O: SearchObserver<T, P> + ?Sized {
+        net::find_paths_between_sets(self, origins, destinations, observer)
+    }
+
+    pub fn validate_path(&self, path: &Path<T>) -> Result<Cost, NetErrors> {
+        net::validate_path(self, path)
+    }
+
+    pub fn shortest_path(&self, origin: &T, destination: &T) -> Result<Path<T>, NetErrors> {
+        self.shortest_path_observed(origin, destination, &mut NoObserver)
+    }
+
+    pub fn shortest_path_observed<O>(&self, origin: &T, destination: &T, observer: &mut O) -> Result<Path<T>, NetErrors>
+        where O: SearchObserver<T, P> + ?Sized {
+        shortest_path::shortest_path_between_sets(self, slice::from_ref(origin), slice::from_ref(destination), observer)
+    }
+
+    pub fn shortest_path_between_sets(&self, origins: &[T], destinations: &[T]) -> Result<Path<T>, NetErrors> {
+        shortest_path::shortest_path_between_sets(self, origins, destinations, &mut NoObserver)
+    }
+
+    pub fn shortest_paths_per_pair(&self, origins: &[T], destinations: &[T]) -> Result<Vec<Path<T>>, NetErrors> {
+        shortest_path::shortest_paths_per_pair(self, origins, destinations, &mut NoObserver)
+    }
+
+    pub fn bidirectional_shortest_path(&self, origin: &T, destination: &T) -> Result<Path<T>, NetErrors> {
+        self.bidirectional_shortest_path_observed(origin, destination, &mut NoObserver)
+    }
+
+    pub fn bidirectional_shortest_path_observed<O>(&self, origin: &T, destination: &T, observer: &mut O) -> Result<Path<T>, NetErrors>
+        where O: SearchObserver<T, P> + ?Sized {
+        bidirectional::bidirectional_shortest_path(self, origin, destination, observer)
+    }
+
+    pub fn all_pairs_shortest_paths(&self) -> ShortestPathMatrix<T> {
+        all_pairs::all_pairs_shortest_paths(self)
+    }
+
+    fn position_or_throws(&self, id: &T::Identifier) -> Result<usize, NetErrors> {
+        self.position_of(id)
+            .ok_or_else(|| NetErrors::PointNotFound(id.to_string()))
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, P> CompactNet<T, P> where T: Point + Send + Sync, T::Identifier: Sync, P: Sync {
+    pub fn par_find_paths(&self, origin: &T, destination: &T) -> Result<Vec<Path<T>>, NetErrors> {
+        net::par_find_paths(self, origin, destination)
+    }
+}
+
+impl<T: Point, P> Topology<T> for CompactNet<T, P> {
+    type Payload = P;
+
+    fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    fn position_of(&self, id: &T::Identifier) -> Option<usize> {
+        self.positions.get(id).cloned()
+    }
+
+    fn point_at(&self, position: usize) -> &T {
+        &self.points[position]
+    }
+
+    fn connections(&self, position: usize) -> &[(usize, Cost)] {
+        self.adjacency.connections(position)
+    }
+
+    fn label(&self, position: usize, connection: usize) -> Option<&str> {
+        self.labels.get(self.adjacency.entry(position, connection)).map(String::as_str)
+    }
+
+    fn payload(&self, position: usize, connection: usize) -> &P {
+        &self.payloads[self.adjacency.entry(position, connection)]
+    }
+
+    fn timetable(&self, position: usize, connection: usize) -> Option<&Timetable> {
+        self.timetables.get(self.adjacency.entry(position, connection))
+    }
+
+    fn resources(&self, position: usize, connection: usize) -> &[Cost] {
+        self.resources.get(self.adjacency.entry(position, connection)).map_or(&[], |resources| &resources[..])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use compact::*;
+    use node::Node;
+    use node::NodeBuilder;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this net of points:
+    // A -1- B -1- C
+    //  \    |    /
+    //   5   1   1
+    //    \  |  /
+    //       D
+    fn weighted_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', vec![('B', 1), ('D', 5)]),
+                node('B', vec![('A', 1), ('C', 1), ('D', 1)]),
+                node('C', vec![('B', 1), ('D', 1)]),
+                node('D', vec![('A', 5), ('C', 1), ('B', 1)]),
+            ]
+        }
+    }
+
+    #[test]
+    fn compact_net_should_keep_each_point_once() {
+        let compact_net = weighted_net().compact();
+
+        assert_eq!(compact_net.len(), 4);
+        assert_eq!(compact_net.connection_count(), 10);
+    }
+
+    #[test]
+    fn compact_net_should_find_the_same_paths_as_the_net() {
+        let net = weighted_net();
+        let compact_net = net.compact();
+        let (a, c) = (simple_point('A'), simple_point('C'));
+
+        assert_eq!(format_paths(compact_net.find_paths(&a, &c).unwrap()), format_paths(net.find_paths(&a, &c).unwrap()));
+        assert_eq!(format!("{}", compact_net.shortest_path(&a, &c).unwrap()), "A-B-C");
+        assert_eq!(format!("{}", compact_net.bidirectional_shortest_path(&a, &c).unwrap()), "A-B-C");
+    }
+
+    #[test]
+    fn compact_net_should_validate_paths() {
+        let compact_net = weighted_net().compact();
+        let path = compact_net.shortest_path(&simple_point('A'), &simple_point('D')).unwrap();
+
+        assert_eq!(compact_net.validate_path(&path).unwrap(), 2);
+    }
+
+    #[test]
+    fn compact_net_should_be_queried_by_point_id() {
+        let compact_net = weighted_net().compact();
+
+        assert!(compact_net.contains(&'A'));
+        assert!(!compact_net.contains(&'E'));
+        assert_eq!(compact_net.point(&'C'), Some(&simple_point('C')));
+        assert_eq!(compact_net.neighbors(&'C').unwrap(), vec![&simple_point('B'), &simple_point('D')]);
+        assert_eq!(format!("{}", compact_net.shortest_path_by_id(&'A', &'C').unwrap()), "A-B-C");
+    }
+
+    // Given this net of points, with a ferry and then a bus and a train from B to C:
+    // A -ferry- B =bus/train= C
+    #[test]
+    fn compact_net_should_keep_the_payloads_and_labels_of_the_connections() {
+        let (a, b, c) = (simple_point('A'), simple_point('B'), simple_point('C'));
+        let net: Net<SimplePoint, &str> = Net {
+            nodes: vec![
+                NodeBuilder::default().point(&a).connected_point_with_payload(&b, 2, "ferry").build().unwrap(),
+                NodeBuilder::default()
+                    .point(&b)
+                    .connected_point_with_label(&c, "bus", 3)
+                    .connected_point_with_label(&c, "train", 2)
+                    .build()
+                    .unwrap(),
+                NodeBuilder::default().point(&c).build().unwrap(),
+            ]
+        };
+
+        let compact_net = net.compact();
+        let path = compact_net.shortest_path(&a, &c).expect("should find a path from A to C");
+
+        assert_eq!(compact_net.payload_between(&a, &b), Some(&"ferry"));
+        assert_eq!(compact_net.payload_between(&b, &c), Some(&""));
+        assert_eq!(path.labels(), &[None, Some(String::from("train"))]);
+    }
+
+    fn format_paths(paths: Vec<Path<SimplePoint>>) -> Vec<String> {
+        paths.iter().map(|path| format!("{}", path)).collect()
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connections: Vec<(char, Cost)>) -> Node<SimplePoint> {
+        let mut builder = NodeBuilder::new();
+        builder.point(&simple_point(name));
+        connections.into_iter()
+            .for_each(|(to, cost)| {
+                builder.connected_point_with_cost(&simple_point(to), cost);
+            });
+        builder.build().unwrap()
+    }
+}
@@ -0,0 +1,210 @@
+use compact::CompactNet;
+use index::NetIndex;
+use index::Topology;
+use net::Net;
+use net::NetErrors;
+use node::Cost;
+use node::Point;
+use path::Path;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Partial path of a label setting search, with the resources it used to get to its last point
+/// and the index of the connection it took from the point of the previous label.
+pub(crate) struct Label {
+    pub position: usize,
+    pub resources: Vec<Cost>,
+    pub previous: Option<(usize, usize)>,
+}
+
+impl Label {
+    pub fn dominates(&self, other_label: &Label) -> bool {
+        self.resources.iter()
+            .zip(&other_label.resources)
+            .all(|(resource, other_resource)| resource <= other_resource)
+    }
+}
+
+impl<T: Point, P> Net<T, P> {
+    /// Finds the path using the least of the `minimized` resource whose use of every resource
+    /// is within its limit. Connections use none of the resources they were not given.
+    /// Fails with `NetErrors::InvalidResource` if `minimized` is not the index of one of the limits.
+    pub fn resource_constrained_shortest_path(&self, origin: &T, destination: &T, limits: &[Cost], minimized: usize) -> Result<Path<T>, NetErrors> {
+        resource_constrained_shortest_path(&NetIndex::new(self), origin, destination, limits, minimized)
+    }
+}
+
+impl<T: Point, P> CompactNet<T, P> {
+    pub fn resource_constrained_shortest_path(&self, origin: &T, destination: &T, limits: &[Cost], minimized: usize) -> Result<Path<T>, NetErrors> {
+        resource_constrained_shortest_path(self, origin, destination, limits, minimized)
+    }
+}
+
+fn resource_constrained_shortest_path<T, G>(graph: &G, origin: &T, destination: &T, limits: &[Cost], minimized: usize) -> Result<Path<T>, NetErrors>
+    where T: Point, G: Topology<T> {
+    if minimized >= limits.len() {
+        return Err(NetErrors::InvalidResource(minimized));
+    }
+
+    let origin = graph.position(origin)?;
+    let destination = graph.position(destination)?;
+
+    let mut labels = vec![Label { position: origin, resources: vec![0; limits.len()], previous: None }];
+    let mut expanded_labels: Vec<Vec<usize>> = vec![Vec::new(); graph.len()];
+    let mut pending = BinaryHeap::new();
+    pending.push(Reverse((0, 0)));
+
+    while let Some(Reverse((_, label_index))) = pending.pop() {
+        let label = &labels[label_index];
+        if expanded_labels[label.position].iter().any(|&expanded| labels[expanded].dominates(label)) {
+            continue;
+        }
+        if label.position == destination {
+            return Ok(labels_path(graph, &labels, label_index));
+        }
+        expanded_labels[label.position].push(label_index);
+
+        let mut next_labels = Vec::new();
+        for (connection, &(next, _)) in graph.connections(label.position).iter().enumerate() {
+            let resources = add_resources(&label.resources, graph.resources(label.position, connection));
+            if resources.iter().zip(limits).all(|(used, limit)| used <= limit) {
+                next_labels.push(Label { position: next, resources, previous: Some((label_index, connection)) });
+            }
+        }
+
+        for next_label in next_labels {
+            pending.push(Reverse((next_label.resources[minimized], labels.len())));
+            labels.push(next_label);
+        }
+    }
+
+    Err(NetErrors::NoPathFound)
+}
+
+/// Path going through the connections taken by the labels up to `last_label`.
+pub(crate) fn labels_path<T: Point, G: Topology<T>>(graph: &G, labels: &[Label], last_label: usize) -> Path<T> {
+    let mut positions = vec![labels[last_label].position];
+    let mut connections = Vec::new();
+    let mut current = last_label;
+    while let Some((previous_label, connection)) = labels[current].previous {
+        positions.push(labels[previous_label].position);
+        connections.push(connection);
+        current = previous_label;
+    }
+    positions.reverse();
+    connections.reverse();
+
+    graph.path_through(&positions, &connections)
+}
+
+/// Resources used after following a connection, counting the ones the connection was not given as zero.
+pub(crate) fn add_resources(used: &[Cost], connection_resources: &[Cost]) -> Vec<Cost> {
+    used.iter()
+        .enumerate()
+        .map(|(resource, used)| used + connection_resources.get(resource).cloned().unwrap_or(0))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use constrained::*;
+    use node::Node;
+    use node::NodeBuilder;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    const TIME: usize = 0;
+    const FUEL: usize = 1;
+
+    // Given this net of points, with the (time, fuel) used by each connection:
+    //    (1,5) B (1,5)
+    //   /           \
+    // A               D
+    //   \           /
+    //    (3,1) C (3,1)
+    fn logistics_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', vec![('B', vec![1, 5]), ('C', vec![3, 1])]),
+                node('B', vec![('D', vec![1, 5])]),
+                node('C', vec![('D', vec![3, 1])]),
+                node('D', vec![]),
+            ]
+        }
+    }
+
+    #[test]
+    fn constrained_shortest_path_should_take_the_quickest_path_within_limits() {
+        let net = logistics_net();
+
+        let path = net.resource_constrained_shortest_path(&simple_point('A'), &simple_point('D'), &[100, 10], TIME)
+            .expect("should find a path from A to D");
+
+        assert_eq!(format!("{}", path), "A-B-D");
+    }
+
+    #[test]
+    fn constrained_shortest_path_should_avoid_paths_over_a_limit() {
+        let net = logistics_net();
+
+        let path = net.resource_constrained_shortest_path(&simple_point('A'), &simple_point('D'), &[100, 4], TIME)
+            .expect("should find a path from A to D");
+
+        assert_eq!(format!("{}", path), "A-C-D", "A-B-D uses 10 of fuel");
+    }
+
+    #[test]
+    fn constrained_shortest_path_should_throw_if_no_path_is_within_limits() {
+        let net = logistics_net();
+
+        match net.resource_constrained_shortest_path(&simple_point('A'), &simple_point('D'), &[5, 4], FUEL) {
+            Err(NetErrors::NoPathFound) => {},
+            other => panic!("NoPathFound expected, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn constrained_shortest_path_should_throw_if_the_minimized_resource_has_no_limit() {
+        let net = logistics_net();
+
+        match net.resource_constrained_shortest_path(&simple_point('A'), &simple_point('D'), &[100, 4], 2) {
+            Err(NetErrors::InvalidResource(resource)) => assert_eq!(resource, 2),
+            other => panic!("InvalidResource expected, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn constrained_shortest_path_should_find_the_same_path_on_a_compact_net() {
+        let compact_net = logistics_net().compact();
+
+        let path = compact_net.resource_constrained_shortest_path(&simple_point('A'), &simple_point('D'), &[100, 4], TIME)
+            .expect("should find a path from A to D");
+
+        assert_eq!(format!("{}", path), "A-C-D");
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connections: Vec<(char, Vec<Cost>)>) -> Node<SimplePoint> {
+        let mut builder = NodeBuilder::new();
+        builder.point(&simple_point(name));
+        connections.into_iter()
+            .for_each(|(to, resources)| {
+                builder.connected_point_with_resources(&simple_point(to), resources[TIME], &resources);
+            });
+        builder.build().unwrap()
+    }
+}
@@ -0,0 +1,333 @@
+use index;
+use net::Net;
+use node::Cost;
+use node::Point;
+use path::Path;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArrowStyle {
+    None,
+    Ascii,
+    Unicode,
+}
+
+/// What a formatter writes on the hop between two points, if the net has the connection
+/// with the label the path recorded for the hop.
+type HopAnnotation<'a, T, A> = Box<dyn Fn(&T, &T, Option<&str>) -> Option<A> + 'a>;
+
+pub struct PathFormatter<'a, T: Point + 'a> {
+    separator: String,
+    label: Box<dyn Fn(&T) -> String + 'a>,
+    cost: Option<HopAnnotation<'a, T, Cost>>,
+    payload: Option<HopAnnotation<'a, T, String>>,
+    arrows: ArrowStyle,
+}
+
+impl<'a, T: Point + 'a> Default for PathFormatter<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Point + 'a> PathFormatter<'a, T> {
+    pub fn new() -> PathFormatter<'a, T> {
+        PathFormatter {
+            separator: String::from("-"),
+            label: Box::new(|point: &T| point.id().to_string()),
+            cost: None,
+            payload: None,
+            arrows: ArrowStyle::None,
+        }
+    }
+
+    pub fn separator(&mut self, separator: &str) -> &mut Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    pub fn label<F>(&mut self, label: F) -> &mut Self
+        where F: Fn(&T) -> String + 'a {
+        self.label = Box::new(label);
+        self
+    }
+
+    /// Annotates each hop with the cost of the connection taken in the given net.
+    pub fn costs<P>(&mut self, net: &'a Net<T, P>) -> &mut Self {
+        let connection = hop_connection(net);
+        self.cost = Some(Box::new(move |from: &T, to: &T, label: Option<&str>| connection(from, to, label)
+            .map(|(cost, _)| cost)));
+        self
+    }
+
+    /// Annotates each hop with its payload in the given net, rendered by `render`.
+    pub fn payloads<P, F>(&mut self, net: &'a Net<T, P>, render: F) -> &mut Self
+        where F: Fn(&P) -> String + 'a {
+        let connection = hop_connection(net);
+        self.payload = Some(Box::new(move |from: &T, to: &T, label: Option<&str>| connection(from, to, label)
+            .map(|(_, payload)| render(payload))));
+        self
+    }
+
+    pub fn arrows(&mut self, arrows: ArrowStyle) -> &mut Self {
+        self.arrows = arrows;
+        self
+    }
+
+    pub fn format(&self, path: &Path<T>) -> String {
+        let points = path.points();
+        let mut formatted = String::new();
+
+        for (position, point) in points.iter().enumerate() {
+            if position > 0 {
+                formatted.push_str(&self.link(&points[position - 1], point, path.labels()[position - 1].as_deref()));
+            }
+            formatted.push_str(&(self.label)(point));
+        }
+
+        formatted
+    }
+
+    /// Renders one row per path with its hop count and, when costs are set, its total cost.
+    pub fn table(&self, paths: &[Path<T>]) -> String {
+        let mut header = vec![String::from("#"), String::from("Hops")];
+        if self.cost.is_some() {
+            header.push(String::from("Cost"));
+        }
+        header.push(String::from("Path"));
+
+        let rows: Vec<Vec<String>> = paths.iter()
+            .enumerate()
+            .map(|(position, path)| {
+                let mut row = vec![(position + 1).to_string(), path.hops().to_string()];
+                if self.cost.is_some() {
+                    row.push(self.total_cost(path));
+                }
+                row.push(self.format(path));
+                row
+            })
+            .collect();
+
+        let widths: Vec<usize> = (0..header.len())
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].chars().count())
+                    .chain(Some(header[column].chars().count()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        Some(&header).into_iter()
+            .chain(rows.iter())
+            .map(|row| Self::table_line(row, &widths))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn link(&self, from: &T, to: &T, label: Option<&str>) -> String {
+        let cost = self.cost.as_ref().map(|cost| match cost(from, to, label) {
+            Some(cost) => cost.to_string(),
+            None => String::from("?")
+        });
+        let payload = self.payload.as_ref().map(|payload| payload(from, to, label).unwrap_or_else(|| String::from("?")));
+        let annotation = match (cost, payload) {
+            (Some(cost), Some(payload)) => Some(format!("{}, {}", cost, payload)),
+            (cost, payload) => cost.or(payload)
+        };
+
+        match (self.arrows, annotation) {
+            (ArrowStyle::None, None) => self.separator.clone(),
+            (ArrowStyle::None, Some(cost)) => format!("{}[{}]{}", self.separator, cost, self.separator),
+            (ArrowStyle::Ascii, None) => String::from("->"),
+            (ArrowStyle::Ascii, Some(cost)) => format!("-[{}]->", cost),
+            (ArrowStyle::Unicode, None) => String::from("→"),
+            (ArrowStyle::Unicode, Some(cost)) => format!("─[{}]→", cost),
+        }
+    }
+
+    fn total_cost(&self, path: &Path<T>) -> String {
+        let cost = match self.cost {
+            Some(ref cost) => cost,
+            None => return String::new()
+        };
+
+        path.points()
+            .windows(2)
+            .zip(path.labels())
+            .map(|(hop, label)| cost(&hop[0], &hop[1], label.as_deref()))
+            .sum::<Option<Cost>>()
+            .map(|cost| cost.to_string())
+            .unwrap_or_else(|| String::from("?"))
+    }
+
+    fn table_line(cells: &[String], widths: &[usize]) -> String {
+        let last = cells.len() - 1;
+        cells.iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(column, (cell, width))| if column == last {
+                cell.clone()
+            } else {
+                format!("{:width$}", cell, width = width)
+            })
+            .collect::<Vec<String>>()
+            .join("  ")
+    }
+}
+
+pub struct PathDisplay<'f, 'a: 'f, T: Point + 'a> {
+    path: &'f Path<T>,
+    formatter: &'f PathFormatter<'a, T>,
+}
+
+impl<'f, 'a, T: Point> fmt::Display for PathDisplay<'f, 'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.formatter.format(self.path))
+    }
+}
+
+impl<T: Point> Path<T> {
+    pub fn display_with<'f, 'a>(&'f self, formatter: &'f PathFormatter<'a, T>) -> PathDisplay<'f, 'a, T> {
+        PathDisplay { path: self, formatter }
+    }
+}
+
+/// Finds the connection a hop took by the position of its first point and the label the path
+/// recorded for it, instead of going through the nodes of the net for every hop.
+fn hop_connection<'a, T: Point + 'a, P>(net: &'a Net<T, P>) -> impl Fn(&T, &T, Option<&str>) -> Option<(Cost, &'a P)> + 'a {
+    let positions = index::positions_of(net);
+    move |from: &T, to: &T, label: Option<&str>| positions.get(&from.id())
+        .and_then(|&position| net.nodes[position].connection_to(to, label))
+}
+
+#[cfg(test)]
+mod test {
+    use format::*;
+    use node::NodeBuilder;
+    use path::PathBuilder;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this net of points:
+    // A -2- B -5- C
+    fn a_b_c_net() -> Net<SimplePoint> {
+        let (a, b, c) = (simple_point('A'), simple_point('B'), simple_point('C'));
+        Net {
+            nodes: vec![
+                NodeBuilder::new().point(&a).connected_point_with_cost(&b, 2).build().unwrap(),
+                NodeBuilder::new().point(&b).connected_point_with_cost(&a, 2).connected_point_with_cost(&c, 5).build().unwrap(),
+                NodeBuilder::new().point(&c).connected_point_with_cost(&b, 5).build().unwrap(),
+            ]
+        }
+    }
+
+    #[test]
+    fn default_formatter_should_format_like_display() {
+        let path = path(&['A', 'B', 'C']);
+
+        assert_eq!(PathFormatter::new().format(&path), format!("{}", path));
+    }
+
+    #[test]
+    fn formatter_should_use_separator_and_label() {
+        let path = path(&['A', 'B', 'C']);
+        let mut formatter = PathFormatter::new();
+        formatter.separator(" / ")
+            .label(|point: &SimplePoint| point.name.to_lowercase().to_string());
+
+        assert_eq!(format!("{}", path.display_with(&formatter)), "a / b / c");
+    }
+
+    #[test]
+    fn formatter_should_annotate_hop_costs_with_arrows() {
+        let net = a_b_c_net();
+        let path = path(&['A', 'B', 'C']);
+        let mut formatter = PathFormatter::new();
+        formatter.costs(&net).arrows(ArrowStyle::Ascii);
+
+        assert_eq!(formatter.format(&path), "A-[2]->B-[5]->C");
+    }
+
+    #[test]
+    fn formatter_should_render_paths_as_a_table() {
+        let net = a_b_c_net();
+        let paths = vec![path(&['A', 'B', 'C']), path(&['B', 'C'])];
+        let mut formatter = PathFormatter::new();
+        formatter.costs(&net);
+
+        let expected = "#  Hops  Cost  Path\n\
+                        1  2     7     A-[2]-B-[5]-C\n\
+                        2  1     5     B-[5]-C";
+        assert_eq!(formatter.table(&paths), expected);
+    }
+
+    #[test]
+    fn formatter_should_annotate_hop_payloads_after_their_costs() {
+        let (a, b) = (simple_point('A'), simple_point('B'));
+        let net: Net<SimplePoint, &str> = Net {
+            nodes: vec![
+                NodeBuilder::default().point(&a).connected_point_with_payload(&b, 2, "ferry").build().unwrap(),
+                NodeBuilder::default().point(&b).build().unwrap(),
+            ]
+        };
+        let mut formatter = PathFormatter::new();
+        formatter.costs(&net)
+            .payloads(&net, |mode: &&str| mode.to_string())
+            .arrows(ArrowStyle::Unicode);
+
+        assert_eq!(formatter.format(&path(&['A', 'B'])), "A─[2, ferry]→B");
+    }
+
+    // Given this net of points, with a bus and a train from A to B:
+    // A =3/2= B -1- C
+    #[test]
+    fn formatter_should_annotate_the_parallel_connection_taken() {
+        let (a, b, c) = (simple_point('A'), simple_point('B'), simple_point('C'));
+        let net = Net {
+            nodes: vec![
+                NodeBuilder::new()
+                    .point(&a)
+                    .connected_point_with_label(&b, "bus", 3)
+                    .connected_point_with_label(&b, "train", 2)
+                    .build()
+                    .unwrap(),
+                NodeBuilder::new().point(&b).connected_point_with_cost(&c, 1).build().unwrap(),
+                NodeBuilder::new().point(&c).build().unwrap(),
+            ]
+        };
+        let path_by = |label: &str| PathBuilder::new()
+            .points(vec![a, b, c])
+            .labels(vec![Some(String::from(label)), None])
+            .build()
+            .unwrap();
+        let mut formatter = PathFormatter::new();
+        formatter.costs(&net);
+
+        assert_eq!(formatter.format(&path_by("bus")), "A-[3]-B-[1]-C");
+        assert_eq!(formatter.format(&path_by("train")), "A-[2]-B-[1]-C");
+        assert_eq!(formatter.table(&[path_by("bus")]), "#  Hops  Cost  Path\n1  2     4     A-[3]-B-[1]-C");
+    }
+
+    fn path(names: &[char]) -> Path<SimplePoint> {
+        PathBuilder::new()
+            .points(names.iter().map(|name| simple_point(*name)).collect())
+            .build()
+            .unwrap()
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+}
@@ -0,0 +1,158 @@
+use node::Cost;
+use node::NodeBuilder;
+use node::Point;
+use path::Path;
+
+/// Mean radius of the Earth, in meters.
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Point at a location on the Earth, in degrees.
+pub trait GeoPoint: Point {
+    fn latitude(&self) -> f64;
+
+    fn longitude(&self) -> f64;
+
+    /// Great circle distance to the other point, in meters.
+    fn distance_to(&self, other: &Self) -> f64 {
+        haversine_distance(self.latitude(), self.longitude(), other.latitude(), other.longitude())
+    }
+}
+
+pub fn haversine_distance(from_latitude: f64, from_longitude: f64, to_latitude: f64, to_longitude: f64) -> f64 {
+    let (from_latitude, to_latitude) = (from_latitude.to_radians(), to_latitude.to_radians());
+    let latitude_delta = to_latitude - from_latitude;
+    let longitude_delta = (to_longitude - from_longitude).to_radians();
+
+    let a = (latitude_delta / 2.0).sin().powi(2)
+        + from_latitude.cos() * to_latitude.cos() * (longitude_delta / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * a.sqrt().min(1.0).asin()
+}
+
+/// Cost of a connection between the points, as their distance in meters rounded up.
+pub fn distance_cost<T: GeoPoint>(from: &T, to: &T) -> Cost {
+    from.distance_to(to).ceil() as Cost
+}
+
+/// A* heuristic estimating the distance to the destination rounded down, so it never
+/// estimates more than a path whose connections cost at least their `distance_cost`.
+/// `astar_shortest_path` only finds the cheapest path with it in nets built that way,
+/// such as with `connected_point_by_distance`, and not with the default cost.
+pub fn distance_heuristic<'a, T: GeoPoint>(destination: &'a T) -> impl Fn(&T) -> Cost + 'a {
+    move |point: &T| point.distance_to(destination).floor() as Cost
+}
+
+impl<T: GeoPoint, P: Clone + Default> NodeBuilder<T, P> {
+    /// Connects the point with its `distance_cost` from the point of the node.
+    ///
+    /// Panics if the point of the node was not set before.
+    pub fn connected_point_by_distance(&mut self, point: &T) -> &mut Self {
+        let cost = distance_cost(self.built_point().expect("should set the point before connecting by distance"), point);
+        self.connected_point_with_cost(point, cost)
+    }
+}
+
+impl<T: GeoPoint> Path<T> {
+    /// GeoJSON LineString geometry going through the points of the path.
+    pub fn to_geojson(&self) -> String {
+        let coordinates: Vec<String> = self.points().iter()
+            .map(|point| format!("[{},{}]", point.longitude(), point.latitude()))
+            .collect();
+        format!("{{\"type\":\"LineString\",\"coordinates\":[{}]}}", coordinates.join(","))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use geo::*;
+    use net::Net;
+    use node::Node;
+
+    #[derive(Clone, PartialEq, Debug)]
+    struct City {
+        name: &'static str,
+        latitude: f64,
+        longitude: f64,
+    }
+
+    impl Point for City {
+        type Identifier = &'static str;
+
+        fn id(&self) -> &'static str {
+            self.name
+        }
+    }
+
+    impl GeoPoint for City {
+        fn latitude(&self) -> f64 {
+            self.latitude
+        }
+
+        fn longitude(&self) -> f64 {
+            self.longitude
+        }
+    }
+
+    fn madrid() -> City { City { name: "Madrid", latitude: 40.4168, longitude: -3.7038 } }
+    fn zaragoza() -> City { City { name: "Zaragoza", latitude: 41.6488, longitude: -0.8891 } }
+    fn valencia() -> City { City { name: "Valencia", latitude: 39.4699, longitude: -0.3763 } }
+    fn barcelona() -> City { City { name: "Barcelona", latitude: 41.3874, longitude: 2.1686 } }
+
+    // Given these cities connected by their distances:
+    // Madrid - Zaragoza - Barcelona
+    //       \            /
+    //        - Valencia -
+    fn spain_net() -> Net<City> {
+        Net {
+            nodes: vec![
+                node(madrid(), vec![zaragoza(), valencia()]),
+                node(zaragoza(), vec![madrid(), barcelona()]),
+                node(valencia(), vec![madrid(), barcelona()]),
+                node(barcelona(), vec![zaragoza(), valencia()]),
+            ]
+        }
+    }
+
+    #[test]
+    fn distance_should_be_the_great_circle_distance() {
+        let distance = madrid().distance_to(&barcelona());
+
+        assert!((distance - 505_000.0).abs() < 2_000.0, "Madrid is about 505 km from Barcelona, got {}", distance);
+        assert_eq!(madrid().distance_to(&madrid()), 0.0);
+    }
+
+    #[test]
+    fn connections_by_distance_should_cost_their_distance_in_meters() {
+        let net = spain_net();
+
+        assert_eq!(net.cost_between(&madrid(), &zaragoza()), Some(distance_cost(&madrid(), &zaragoza())));
+        assert!(distance_heuristic(&zaragoza())(&madrid()) <= distance_cost(&madrid(), &zaragoza()));
+    }
+
+    #[test]
+    fn astar_with_the_distance_heuristic_should_find_the_same_path_as_dijkstra() {
+        let net = spain_net();
+
+        let path = net.astar_shortest_path(&madrid(), &barcelona(), distance_heuristic(&barcelona()))
+            .expect("should find a path from Madrid to Barcelona");
+
+        assert_eq!(format!("{}", path), "Madrid-Zaragoza-Barcelona");
+        assert_eq!(format!("{}", path), format!("{}", net.shortest_path(&madrid(), &barcelona()).unwrap()));
+    }
+
+    #[test]
+    fn path_should_be_exported_as_a_geojson_line_string() {
+        let path = spain_net().shortest_path(&madrid(), &zaragoza()).unwrap();
+
+        assert_eq!(path.to_geojson(), "{\"type\":\"LineString\",\"coordinates\":[[-3.7038,40.4168],[-0.8891,41.6488]]}");
+    }
+
+    fn node(city: City, connected_to: Vec<City>) -> Node<City> {
+        let mut builder = NodeBuilder::new();
+        builder.point(&city);
+        connected_to.iter()
+            .for_each(|to| {
+                builder.connected_point_by_distance(to);
+            });
+        builder.build().unwrap()
+    }
+}
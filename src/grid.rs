@@ -0,0 +1,217 @@
+use net::Net;
+use node::Cost;
+use node::NodeBuilder;
+use node::Point;
+use node::DEFAULT_COST;
+use std::fmt;
+
+/// Cell of a grid, by its column and row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GridPoint {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl GridPoint {
+    pub fn new(x: usize, y: usize) -> GridPoint {
+        GridPoint { x, y }
+    }
+}
+
+impl fmt::Display for GridPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Point for GridPoint {
+    type Identifier = GridPoint;
+
+    fn id(&self) -> GridPoint {
+        *self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Connectivity {
+    /// Cells are connected to the cells on their sides.
+    Four,
+    /// Cells are also connected to the cells on their corners, unless that cuts the corner of an obstacle.
+    Eight,
+    /// Rows of hexagons where odd rows are shifted half a cell to the right.
+    Hex,
+}
+
+/// Generates a net with a point for each free cell of a grid, connected to its free neighbors.
+pub struct GridBuilder<'a> {
+    width: usize,
+    height: usize,
+    connectivity: Connectivity,
+    straight_cost: Cost,
+    diagonal_cost: Cost,
+    obstacles: Box<dyn Fn(&GridPoint) -> bool + 'a>,
+}
+
+impl<'a> GridBuilder<'a> {
+    pub fn new(width: usize, height: usize) -> GridBuilder<'a> {
+        GridBuilder {
+            width,
+            height,
+            connectivity: Connectivity::Four,
+            straight_cost: DEFAULT_COST,
+            diagonal_cost: DEFAULT_COST,
+            obstacles: Box::new(|_| false),
+        }
+    }
+
+    pub fn connectivity(&mut self, connectivity: Connectivity) -> &mut Self {
+        self.connectivity = connectivity;
+        self
+    }
+
+    /// Costs of the connections to the cells on the sides and on the corners,
+    /// such as 10 and 14 to approximate the length of the diagonals.
+    pub fn costs(&mut self, straight_cost: Cost, diagonal_cost: Cost) -> &mut Self {
+        self.straight_cost = straight_cost;
+        self.diagonal_cost = diagonal_cost;
+        self
+    }
+
+    pub fn obstacles<F>(&mut self, is_obstacle: F) -> &mut Self
+        where F: Fn(&GridPoint) -> bool + 'a {
+        self.obstacles = Box::new(is_obstacle);
+        self
+    }
+
+    /// Obstacles given by row, where `true` is an obstacle. Cells outside the bitmap are free.
+    pub fn obstacle_bitmap(&mut self, bitmap: &'a [Vec<bool>]) -> &mut Self {
+        self.obstacles(move |cell: &GridPoint| bitmap.get(cell.y)
+            .and_then(|row| row.get(cell.x))
+            .cloned()
+            .unwrap_or(false))
+    }
+
+    /// Builds the net with the cells by row, skipping the obstacles.
+    pub fn build(&self) -> Net<GridPoint> {
+        let mut nodes = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let cell = GridPoint::new(x, y);
+                if !self.is_free(x as isize, y as isize) {
+                    continue;
+                }
+
+                let mut builder = NodeBuilder::new();
+                builder.point(&cell);
+                for (neighbor, cost) in self.neighbors(&cell) {
+                    builder.connected_point_with_cost(&neighbor, cost);
+                }
+                nodes.push(builder.build().expect("a cell is never its own neighbor"));
+            }
+        }
+
+        Net { nodes }
+    }
+
+    fn neighbors(&self, cell: &GridPoint) -> Vec<(GridPoint, Cost)> {
+        const SIDES: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+        const CORNERS: [(isize, isize); 4] = [(1, 1), (-1, 1), (-1, -1), (1, -1)];
+        const EVEN_ROW_HEXES: [(isize, isize); 6] = [(1, 0), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1)];
+        const ODD_ROW_HEXES: [(isize, isize); 6] = [(1, 0), (1, 1), (0, 1), (-1, 0), (0, -1), (1, -1)];
+
+        let (x, y) = (cell.x as isize, cell.y as isize);
+        let mut offsets: Vec<((isize, isize), Cost)> = Vec::new();
+        match self.connectivity {
+            Connectivity::Four => offsets.extend(SIDES.iter().map(|&offset| (offset, self.straight_cost))),
+            Connectivity::Eight => {
+                offsets.extend(SIDES.iter().map(|&offset| (offset, self.straight_cost)));
+                offsets.extend(CORNERS.iter()
+                    .filter(|&&(dx, dy)| self.is_free(x + dx, y) && self.is_free(x, y + dy))
+                    .map(|&offset| (offset, self.diagonal_cost)));
+            },
+            Connectivity::Hex => {
+                let hexes = if y % 2 == 0 { &EVEN_ROW_HEXES } else { &ODD_ROW_HEXES };
+                offsets.extend(hexes.iter().map(|&offset| (offset, self.straight_cost)));
+            },
+        }
+
+        offsets.into_iter()
+            .filter(|&((dx, dy), _)| self.is_free(x + dx, y + dy))
+            .map(|((dx, dy), cost)| (GridPoint::new((x + dx) as usize, (y + dy) as usize), cost))
+            .collect()
+    }
+
+    fn is_free(&self, x: isize, y: isize) -> bool {
+        let is_inside = x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height;
+        is_inside && !(self.obstacles)(&GridPoint::new(x as usize, y as usize))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use grid::*;
+
+    #[test]
+    fn four_connected_grid_should_go_around_obstacles() {
+        let bitmap = bitmap(&[
+            "...",
+            ".#.",
+            "...",
+        ]);
+        let net = GridBuilder::new(3, 3).obstacle_bitmap(&bitmap).build();
+
+        assert_eq!(net.nodes.len(), 8);
+        assert!(!net.contains(&GridPoint::new(1, 1)));
+        let path = net.shortest_path(&GridPoint::new(0, 1), &GridPoint::new(2, 1)).expect("should go around the obstacle");
+        assert_eq!(path.hops(), 4);
+    }
+
+    #[test]
+    fn eight_connected_grid_should_weight_diagonals() {
+        let net = GridBuilder::new(3, 3)
+            .connectivity(Connectivity::Eight)
+            .costs(10, 14)
+            .build();
+
+        let path = net.shortest_path(&GridPoint::new(0, 0), &GridPoint::new(2, 2)).expect("should cross the grid");
+        assert_eq!(format!("{}", path), "(0, 0)-(1, 1)-(2, 2)");
+        assert_eq!(net.validate_path(&path).unwrap(), 28);
+        assert_eq!(net.out_degree(&GridPoint::new(1, 1)).unwrap(), 8);
+    }
+
+    #[test]
+    fn eight_connected_grid_should_not_cut_the_corners_of_obstacles() {
+        let net = GridBuilder::new(2, 2)
+            .connectivity(Connectivity::Eight)
+            .obstacles(|cell: &GridPoint| *cell == GridPoint::new(1, 0))
+            .build();
+
+        assert_eq!(net.cost_between(&GridPoint::new(0, 0), &GridPoint::new(1, 1)), None);
+        assert!(net.find_paths(&GridPoint::new(0, 0), &GridPoint::new(1, 1)).is_ok(), "should go through (0, 1)");
+    }
+
+    #[test]
+    fn hex_grid_should_connect_each_cell_to_six_neighbors() {
+        let net = GridBuilder::new(3, 4).connectivity(Connectivity::Hex).build();
+
+        assert_eq!(neighbors(&net, 1, 1), vec![(0, 1), (1, 0), (1, 2), (2, 0), (2, 1), (2, 2)]);
+        assert_eq!(neighbors(&net, 1, 2), vec![(0, 1), (0, 2), (0, 3), (1, 1), (1, 3), (2, 2)]);
+        assert_eq!(neighbors(&net, 0, 0), vec![(0, 1), (1, 0)]);
+    }
+
+    fn neighbors(net: &Net<GridPoint>, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut neighbors: Vec<(usize, usize)> = net.neighbors(&GridPoint::new(x, y))
+            .unwrap()
+            .iter()
+            .map(|cell| (cell.x, cell.y))
+            .collect();
+        neighbors.sort();
+        neighbors
+    }
+
+    fn bitmap(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.chars().map(|cell| cell == '#').collect())
+            .collect()
+    }
+}
@@ -0,0 +1,292 @@
+use net::Net;
+use net::NetErrors;
+use node::Cost;
+use node::Node;
+use node::Point;
+use path::Path;
+use path::PathBuilder;
+use std::collections::HashMap;
+use std::mem;
+use timetable::Timetable;
+
+/// Nets whose points are addressed by their position, so searches can keep
+/// their state in vectors instead of looking up nodes by point on every step.
+pub trait Topology<T: Point> {
+    /// Payload carried by each connection.
+    type Payload;
+
+    fn len(&self) -> usize;
+
+    fn position_of(&self, id: &T::Identifier) -> Option<usize>;
+
+    fn point_at(&self, position: usize) -> &T;
+
+    fn connections(&self, position: usize) -> &[(usize, Cost)];
+
+    /// Label of a connection, by its index in the connections of its point.
+    fn label(&self, position: usize, connection: usize) -> Option<&str>;
+
+    /// Payload of a connection, by its index in the connections of its point.
+    fn payload(&self, position: usize, connection: usize) -> &Self::Payload;
+
+    /// Timetable of a connection, if it only runs at the times of one.
+    fn timetable(&self, position: usize, connection: usize) -> Option<&Timetable>;
+
+    /// Resources a connection uses, empty if it was not given any.
+    fn resources(&self, position: usize, connection: usize) -> &[Cost];
+
+    fn position(&self, point: &T) -> Result<usize, NetErrors> {
+        let id = point.id();
+        self.position_of(&id)
+            .ok_or_else(|| NetErrors::PointNotFound(id.to_string()))
+    }
+
+    fn positions(&self, points: &[T]) -> Result<Vec<usize>, NetErrors> {
+        points.iter()
+            .map(|point| self.position(point))
+            .collect()
+    }
+
+    /// Positions of the points, each one only once even if a point is given twice.
+    fn distinct_positions(&self, points: &[T]) -> Result<Vec<usize>, NetErrors> {
+        let mut positions = self.positions(points)?;
+        let mut seen = vec![false; self.len()];
+        positions.retain(|&position| !mem::replace(&mut seen[position], true));
+        Ok(positions)
+    }
+
+    fn connection_count(&self) -> usize {
+        (0..self.len())
+            .map(|position| self.connections(position).len())
+            .sum()
+    }
+
+    /// Index of the cheapest of the connections between two points, which may be parallel.
+    fn cheapest_connection(&self, from: usize, to: usize) -> Option<usize> {
+        self.connections(from).iter()
+            .enumerate()
+            .filter(|&(_, &(connected_to, _))| connected_to == to)
+            .min_by_key(|&(_, &(_, cost))| cost)
+            .map(|(connection, _)| connection)
+    }
+
+    /// Index of the connection between two points with the given label, or of the cheapest
+    /// one without label, as recorded by the paths for each hop.
+    fn connection_for(&self, from: usize, to: usize, label: Option<&str>) -> Option<usize> {
+        match label {
+            Some(_) => self.connections(from).iter()
+                .enumerate()
+                .position(|(connection, &(connected_to, _))| connected_to == to && self.label(from, connection) == label),
+            None => self.cheapest_connection(from, to)
+        }
+    }
+
+    /// Path going through the cheapest connection between each pair of positions.
+    fn path(&self, positions: &[usize]) -> Path<T> {
+        let connections: Vec<usize> = positions.windows(2)
+            .map(|hop| self.cheapest_connection(hop[0], hop[1]).expect("consecutive positions are connected"))
+            .collect();
+
+        self.path_through(positions, &connections)
+    }
+
+    /// Path going through the given connection of each position but the last one.
+    fn path_through(&self, positions: &[usize], connections: &[usize]) -> Path<T> {
+        PathBuilder::new()
+            .points(positions.iter().map(|position| self.point_at(*position).clone()).collect())
+            .labels(positions.iter()
+                .zip(connections)
+                .map(|(position, connection)| self.label(*position, *connection).map(String::from))
+                .collect())
+            .build()
+            .expect("a path of positions always has points")
+    }
+}
+
+/// Connections of every point stored one after another, with the offset where
+/// the connections of each point start.
+#[derive(Debug)]
+pub struct Adjacency {
+    offsets: Vec<usize>,
+    connections: Vec<(usize, Cost)>,
+    /// Index each connection had among the connections of its point where it was taken from.
+    sources: Vec<usize>,
+}
+
+impl Adjacency {
+    /// Adjacency of the nodes of a net, leaving out the connections to points not in it.
+    pub fn new<T: Point, P>(net: &Net<T, P>, positions: &HashMap<T::Identifier, usize>) -> Adjacency {
+        Adjacency::collect(net.nodes.len(), |position| net.nodes[position].connections()
+            .enumerate()
+            .filter_map(move |(source, (to, cost, _, _))| positions.get(&to.id()).map(|&to| (to, cost, source))))
+    }
+
+    /// Adjacency of a graph with the cost `weight` gives to each connection, from its points,
+    /// its cost and its payload. The connections it gives no cost to are left out.
+    pub fn weighted<T, G, F>(graph: &G, weight: F) -> Adjacency
+        where T: Point, G: Topology<T>, F: Fn(&T, &T, Cost, &G::Payload) -> Option<Cost> {
+        let weight = &weight;
+        Adjacency::collect(graph.len(), |position| graph.connections(position).iter()
+            .enumerate()
+            .filter_map(move |(source, &(to, cost))| weight(graph.point_at(position), graph.point_at(to), cost, graph.payload(position, source))
+                .map(|cost| (to, cost, source))))
+    }
+
+    /// Adjacency of `len` points with the connections `connections_of` gives for each
+    /// position, as the position they go to, their cost and their source index.
+    fn collect<F, I>(len: usize, connections_of: F) -> Adjacency
+        where F: Fn(usize) -> I, I: Iterator<Item=(usize, Cost, usize)> {
+        let mut offsets = Vec::with_capacity(len + 1);
+        let mut connections = Vec::new();
+        let mut sources = Vec::new();
+
+        offsets.push(0);
+        for position in 0..len {
+            for (to, cost, source) in connections_of(position) {
+                connections.push((to, cost));
+                sources.push(source);
+            }
+            offsets.push(connections.len());
+        }
+
+        Adjacency { offsets, connections, sources }
+    }
+
+    /// Connections arriving to each point of a graph, so searches can walk it backwards.
+    /// The source of each one is its index among the connections of the point it leaves.
+    pub fn reversed<T: Point, G: Topology<T>>(graph: &G) -> Adjacency {
+        let mut arriving: Vec<Vec<(usize, Cost, usize)>> = vec![Vec::new(); graph.len()];
+        for from in 0..graph.len() {
+            for (source, &(to, cost)) in graph.connections(from).iter().enumerate() {
+                arriving[to].push((from, cost, source));
+            }
+        }
+
+        Adjacency::collect(arriving.len(), |position| arriving[position].iter().cloned())
+    }
+
+    pub fn connections(&self, position: usize) -> &[(usize, Cost)] {
+        &self.connections[self.offsets[position]..self.offsets[position + 1]]
+    }
+
+    /// Index of a connection among the connections of every point.
+    pub fn entry(&self, position: usize, connection: usize) -> usize {
+        self.offsets[position] + connection
+    }
+
+    /// Index the connection had among the connections of its point where it was taken from.
+    pub fn source(&self, position: usize, connection: usize) -> usize {
+        self.sources[self.entry(position, connection)]
+    }
+}
+
+pub fn positions_of<T: Point, P>(net: &Net<T, P>) -> HashMap<T::Identifier, usize> {
+    net.nodes.iter()
+        .enumerate()
+        .map(|(position, node)| (node.point().id(), position))
+        .collect()
+}
+
+/// Position based view borrowing the nodes of a net, built for a single search.
+pub struct NetIndex<'a, T: Point + 'a, P: 'a> {
+    nodes: &'a [Node<T, P>],
+    positions: HashMap<T::Identifier, usize>,
+    adjacency: Adjacency,
+}
+
+impl<'a, T: Point + 'a, P: 'a> NetIndex<'a, T, P> {
+    pub fn new(net: &'a Net<T, P>) -> NetIndex<'a, T, P> {
+        let positions = positions_of(net);
+        let adjacency = Adjacency::new(net, &positions);
+
+        NetIndex { nodes: &net.nodes, positions, adjacency }
+    }
+}
+
+impl<'a, T: Point + 'a, P: 'a> Topology<T> for NetIndex<'a, T, P> {
+    type Payload = P;
+
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn position_of(&self, id: &T::Identifier) -> Option<usize> {
+        self.positions.get(id).cloned()
+    }
+
+    fn point_at(&self, position: usize) -> &T {
+        self.nodes[position].point()
+    }
+
+    fn connections(&self, position: usize) -> &[(usize, Cost)] {
+        self.adjacency.connections(position)
+    }
+
+    fn label(&self, position: usize, connection: usize) -> Option<&str> {
+        self.nodes[position].label(self.adjacency.source(position, connection))
+    }
+
+    fn payload(&self, position: usize, connection: usize) -> &P {
+        self.nodes[position].payload(self.adjacency.source(position, connection))
+    }
+
+    fn timetable(&self, position: usize, connection: usize) -> Option<&Timetable> {
+        self.nodes[position].timetable(self.adjacency.source(position, connection))
+    }
+
+    fn resources(&self, position: usize, connection: usize) -> &[Cost] {
+        self.nodes[position].resources(self.adjacency.source(position, connection))
+    }
+}
+
+/// View of a graph with the costs `weight` gives to its connections, leaving out
+/// the connections it gives no cost to.
+pub struct Reweighted<'g, G: 'g> {
+    graph: &'g G,
+    adjacency: Adjacency,
+}
+
+impl<'g, G: 'g> Reweighted<'g, G> {
+    pub fn new<T, F>(graph: &'g G, weight: F) -> Reweighted<'g, G>
+        where T: Point, G: Topology<T>, F: Fn(&T, &T, Cost, &G::Payload) -> Option<Cost> {
+        let adjacency = Adjacency::weighted(graph, weight);
+
+        Reweighted { graph, adjacency }
+    }
+}
+
+impl<'g, T: Point, G: Topology<T> + 'g> Topology<T> for Reweighted<'g, G> {
+    type Payload = G::Payload;
+
+    fn len(&self) -> usize {
+        self.graph.len()
+    }
+
+    fn position_of(&self, id: &T::Identifier) -> Option<usize> {
+        self.graph.position_of(id)
+    }
+
+    fn point_at(&self, position: usize) -> &T {
+        self.graph.point_at(position)
+    }
+
+    fn connections(&self, position: usize) -> &[(usize, Cost)] {
+        self.adjacency.connections(position)
+    }
+
+    fn label(&self, position: usize, connection: usize) -> Option<&str> {
+        self.graph.label(position, self.adjacency.source(position, connection))
+    }
+
+    fn payload(&self, position: usize, connection: usize) -> &G::Payload {
+        self.graph.payload(position, self.adjacency.source(position, connection))
+    }
+
+    fn timetable(&self, position: usize, connection: usize) -> Option<&Timetable> {
+        self.graph.timetable(position, self.adjacency.source(position, connection))
+    }
+
+    fn resources(&self, position: usize, connection: usize) -> &[Cost] {
+        self.graph.resources(position, self.adjacency.source(position, connection))
+    }
+}
@@ -1,6 +1,29 @@
 #[macro_use]
 extern crate quick_error;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 
 pub mod node;
 pub mod path;
 pub mod net;
+pub mod format;
+pub mod all_pairs;
+pub mod compact;
+pub mod observer;
+pub mod stats;
+pub mod cancel;
+pub mod page;
+pub mod order;
+pub mod timetable;
+pub mod constrained;
+pub mod pareto;
+pub mod turns;
+pub mod payload;
+pub mod astar;
+pub mod geo;
+pub mod spatial;
+pub mod grid;
+mod bidirectional;
+mod shortest_path;
+mod trail;
+mod index;
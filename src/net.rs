@@ -1,81 +1,259 @@
+use index::NetIndex;
+use index::Topology;
+use node::Cost;
 use node::Node;
 use node::Point;
-use path::PathBuilder;
+use observer::NoObserver;
+use observer::SearchControl;
+use observer::SearchObserver;
 use path::Path;
+use std::rc::Rc;
+use std::slice;
+use trail::Trail;
 
+/// Points and their connections, each connection carrying a payload of type `P`.
 #[derive(Debug)]
-pub struct Net<T: Point> {
-    pub nodes: Vec<Node<T>>
+pub struct Net<T: Point, P = ()> {
+    pub nodes: Vec<Node<T, P>>
 }
 
-impl<'a, T: Point> Net<T> {
+impl<'a, T: Point, P> Net<T, P> {
     pub fn find_paths(&self, origin: &'a T, destination: &'a T) -> Result<Vec<Path<T>>, NetErrors> {
-        let node_from = self.find_node_or_throws(origin)?;
+        self.find_paths_observed(origin, destination, &mut NoObserver)
+    }
+
+    pub fn find_paths_observed<O>(&self, origin: &'a T, destination: &'a T, observer: &mut O) -> Result<Vec<Path<T>>, NetErrors>
+        where O: SearchObserver<T, P> + ?Sized {
+        find_paths_between_sets(&NetIndex::new(self), slice::from_ref(origin), slice::from_ref(destination), observer)
+    }
+
+    /// Finds all the paths starting at any of the origins and ending at any of the destinations,
+    /// exploring the net once per origin. A point that is both an origin and a destination is
+    /// never a path on its own, as no path goes from a point to itself.
+    pub fn find_paths_between_sets(&self, origins: &[T], destinations: &[T]) -> Result<Vec<Path<T>>, NetErrors> {
+        self.find_paths_between_sets_observed(origins, destinations, &mut NoObserver)
+    }
+
+    pub fn find_paths_between_sets_observed<O>(&self, origins: &[T], destinations: &[T], observer: &mut O) -> Result<Vec<Path<T>>, NetErrors>
+        where O: SearchObserver<T, P> + ?Sized {
+        find_paths_between_sets(&NetIndex::new(self), origins, destinations, observer)
+    }
 
-        let path_starting_with_origin_point = PathBuilder::new().point(origin).build();
+    pub fn find_paths_by_id(&self, origin: &T::Identifier, destination: &T::Identifier) -> Result<Vec<Path<T>>, NetErrors> {
+        self.find_paths(self.point_or_throws(origin)?, self.point_or_throws(destination)?)
+    }
 
-        match path_starting_with_origin_point {
-            Err(message) => Err(NetErrors::PathCannotBeBuilt(message)),
-            Ok(beginning_path) => match self.find_paths_not_crossing_previous_path(&node_from, &destination, &beginning_path) {
-                Some(paths) => Ok(paths),
-                None => Err(NetErrors::NoPathFound)
+    pub fn shortest_path_by_id(&self, origin: &T::Identifier, destination: &T::Identifier) -> Result<Path<T>, NetErrors> {
+        self.shortest_path(self.point_or_throws(origin)?, self.point_or_throws(destination)?)
+    }
+
+    pub fn validate_path(&self, path: &Path<T>) -> Result<Cost, NetErrors> {
+        validate_path(&NetIndex::new(self), path)
+    }
+
+    pub fn contains(&self, id: &T::Identifier) -> bool {
+        self.find_node_by_id(id).is_some()
+    }
+
+    pub fn point(&self, id: &T::Identifier) -> Option<&T> {
+        self.find_node_by_id(id).map(|node| node.point())
+    }
+
+    pub fn neighbors(&self, id: &T::Identifier) -> Result<Vec<&T>, NetErrors> {
+        Ok(self.find_node_by_id_or_throws(id)?.neighbors().collect())
+    }
+
+    pub fn iter_points(&self) -> impl Iterator<Item=&T> {
+        self.nodes.iter()
+            .map(|node| node.point())
+    }
+
+    pub fn out_degree(&self, id: &T::Identifier) -> Result<usize, NetErrors> {
+        Ok(self.find_node_by_id_or_throws(id)?.degree())
+    }
+
+    /// Connections to the point, counting each of the parallel ones as `out_degree` does.
+    pub fn in_degree(&self, id: &T::Identifier) -> Result<usize, NetErrors> {
+        let point = self.find_node_by_id_or_throws(id)?.point();
+        Ok(self.nodes.iter()
+            .flat_map(|node| node.neighbors())
+            .filter(|neighbor| neighbor.is(point))
+            .count())
+    }
+
+    pub fn cost_between(&self, from: &T, to: &T) -> Option<Cost> {
+        self.nodes.iter()
+            .find(|node| node.point_is(from))
+            .and_then(|node| node.cost_to(to))
+    }
+
+    /// Payload of the cheapest connection between the points.
+    pub fn payload_between(&self, from: &T, to: &T) -> Option<&P> {
+        self.nodes.iter()
+            .find(|node| node.point_is(from))
+            .and_then(|node| node.payload_to(to))
+    }
+
+    fn find_node_by_id(&self, id: &T::Identifier) -> Option<&Node<T, P>> {
+        self.nodes.iter()
+            .find(|node| node.point().id() == *id)
+    }
+
+    fn find_node_by_id_or_throws(&self, id: &T::Identifier) -> Result<&Node<T, P>, NetErrors> {
+        self.find_node_by_id(id)
+            .ok_or_else(|| NetErrors::PointNotFound(id.to_string()))
+    }
+
+    fn point_or_throws(&self, id: &T::Identifier) -> Result<&T, NetErrors> {
+        self.find_node_by_id_or_throws(id).map(|node| node.point())
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, P> Net<T, P> where T: Point + Send + Sync, T::Identifier: Sync, P: Sync {
+    /// Finds the same paths as `find_paths`, exploring each connection of the origin in parallel.
+    pub fn par_find_paths(&self, origin: &T, destination: &T) -> Result<Vec<Path<T>>, NetErrors> {
+        par_find_paths(&NetIndex::new(self), origin, destination)
+    }
+}
+
+pub(crate) fn find_paths_between_sets<T, G, O>(graph: &G, origins: &[T], destinations: &[T], observer: &mut O) -> Result<Vec<Path<T>>, NetErrors>
+    where T: Point, G: Topology<T>, O: SearchObserver<T, G::Payload> + ?Sized {
+    let origins = graph.positions(origins)?;
+    let destinations = graph.distinct_positions(destinations)?;
+
+    let mut pending_trails: Vec<Rc<Trail>> = origins.into_iter()
+        .rev()
+        .map(Trail::start)
+        .collect();
+    let mut paths = Vec::new();
+    let control = follow_trails_to_destinations(graph, &destinations, &mut pending_trails, &mut paths, usize::MAX, &|_, _| true, observer);
+
+    if paths.is_empty() && control == SearchControl::Stop {
+        Err(NetErrors::Stopped)
+    } else if paths.is_empty() {
+        Err(NetErrors::NoPathFound)
+    } else {
+        Ok(paths)
+    }
+}
+
+#[cfg(feature = "rayon")]
+pub(crate) fn par_find_paths<T, G>(graph: &G, origin: &T, destination: &T) -> Result<Vec<Path<T>>, NetErrors>
+    where T: Point + Send, G: Topology<T> + Sync {
+    use rayon::prelude::*;
+
+    let origin = graph.position(origin)?;
+    let destinations = vec![graph.position(destination)?];
+
+    let paths: Vec<Path<T>> = graph.connections(origin)
+        .par_iter()
+        .enumerate()
+        .map(|(connection, &(point, _))| {
+            let mut paths = Vec::new();
+            let mut pending_trails = vec![Trail::extend(&Trail::start(origin), point, connection)];
+            follow_trails_to_destinations(graph, &destinations, &mut pending_trails, &mut paths, usize::MAX, &|_, _| true, &mut NoObserver);
+            paths
+        })
+        .collect::<Vec<Vec<Path<T>>>>()
+        .into_iter()
+        .flatten()
+        .collect();
+
+    if paths.is_empty() {
+        Err(NetErrors::NoPathFound)
+    } else {
+        Ok(paths)
+    }
+}
+
+/// Depth first search keeping the pending trails in an explicit stack, so the length
+/// of the paths is limited by the heap and not by the thread stack size. The search pauses
+/// once `paths` holds `path_limit` paths, leaving the trails still to follow in the stack.
+/// Trails are only extended to the positions `can_follow` accepts.
+pub(crate) fn follow_trails_to_destinations<T, G, O>(graph: &G, destinations: &[usize], pending_trails: &mut Vec<Rc<Trail>>,
+                                                     paths: &mut Vec<Path<T>>, path_limit: usize,
+                                                     can_follow: &dyn Fn(&Trail, usize) -> bool, observer: &mut O) -> SearchControl
+    where T: Point, G: Topology<T>, O: SearchObserver<T, G::Payload> + ?Sized {
+    while paths.len() < path_limit {
+        let following_trail = match pending_trails.pop() {
+            Some(trail) => trail,
+            None => break
+        };
+        let last_position = following_trail.last_position();
+        let reaches_destination = following_trail.hops() > 0 && destinations.contains(&last_position);
+
+        if reaches_destination {
+            let path = following_trail.to_path(graph);
+            let control = observer.on_path_found(&path);
+            paths.push(path);
+            if control == SearchControl::Stop {
+                return control;
             }
         }
-    }
 
-    fn find_paths_not_crossing_previous_path(&self, origin: &Node<T>, destination: &T, previous_path: &Path<T>) -> Option<Vec<Path<T>>> {
-        match origin.connected_points_not_in_path(previous_path) {
-            None => None,
-            Some(followable_points) => {
-                let paths = self.all_paths_to_destination_following_path_and_continuing_with_points(&destination, previous_path, followable_points);
+        if reaches_destination && stops_at_destination(&following_trail, destinations) {
+            continue;
+        }
 
-                if paths.is_empty() {
-                    None
-                } else {
-                    Some(paths)
+        let last_point = graph.point_at(last_position);
+        let arrived_through = following_trail.arrived_through()
+            .map(|(previous_position, connection)| graph.payload(previous_position, connection));
+        if observer.on_expand(last_point, following_trail.hops(), arrived_through) == SearchControl::Stop {
+            return SearchControl::Stop;
+        }
+
+        let mut followable_connections = Vec::new();
+        for (connection, &(position, _)) in graph.connections(last_position).iter().enumerate() {
+            if following_trail.contains(position) {
+                let payload = graph.payload(last_position, connection);
+                if observer.on_prune(last_point, graph.point_at(position), payload) == SearchControl::Stop {
+                    return SearchControl::Stop;
                 }
+            } else if can_follow(&following_trail, position) {
+                followable_connections.push((position, connection));
             }
         }
-    }
 
-    fn all_paths_to_destination_following_path_and_continuing_with_points(&self, destination: &&T, previous_path: &Path<T>, followable_points: Vec<&T>) -> Vec<Path<T>> {
-        followable_points
+        pending_trails.extend(followable_connections
             .into_iter()
-            .map(|point| self.all_paths_to_destination_following_path_and_continuing_with_point(&destination, previous_path, point))
-            .fold(Vec::new(), |paths: Vec<Path<T>>, path_search: Option<Vec<Path<T>>>|
-                match path_search {
-                    Some(paths_found) => paths.into_iter().chain(paths_found.into_iter()).collect(),
-                    None => paths
-                },
-            )
-    }
-
-    fn all_paths_to_destination_following_path_and_continuing_with_point(&self, destination: &T, following_path: &Path<T>, next_point: &T) -> Option<Vec<Path<T>>> {
-        let origin_node = self.find_node_or_panic(next_point);
-        let trying_path = following_path.with_point_at_the_end(next_point);
-        if trying_path.ends_with(destination) {
-            Some(vec![trying_path])
-        } else {
-            self.find_paths_not_crossing_previous_path(origin_node, &destination, &trying_path)
-        }
+            .rev()
+            .map(|(position, connection)| Trail::extend(&following_trail, position, connection)));
     }
 
-    fn find_node_or_throws(&self, point: &T) -> Result<&Node<T>, NetErrors> {
-        let node_point = self.nodes.iter()
-            .find(|node| node.point_is(point));
+    SearchControl::Continue
+}
+
+/// Trails reaching a destination go on only while other destinations are still to be reached,
+/// as a trail cannot go through a point twice.
+pub(crate) fn stops_at_destination(trail: &Trail, destinations: &[usize]) -> bool {
+    destinations.iter().all(|&destination| trail.contains(destination))
+}
 
-        match node_point {
-            Some(ref node) => Ok(node),
-            None => Err(NetErrors::PointNotFound(point.id().to_string()))
+pub(crate) fn validate_path<T: Point, G: Topology<T>>(graph: &G, path: &Path<T>) -> Result<Cost, NetErrors> {
+    let points = path.points();
+    let mut previous_position = match points.first() {
+        Some(first_point) => graph.position(first_point)?,
+        None => return Ok(0)
+    };
+
+    let mut cost = 0;
+    for (position_in_path, point) in points.iter().enumerate().skip(1) {
+        if points[..position_in_path].iter().any(|previous_point| previous_point.is(point)) {
+            return Err(NetErrors::DuplicatePoint(point.id().to_string()));
         }
-    }
 
-    fn find_node_or_panic(&self, point: &T) -> &Node<T> {
-        match self.find_node_or_throws(point) {
-            Ok(ref node) => node,
-            Err(err) => panic!(err)
+        let previous_point = &points[position_in_path - 1];
+        let label = path.labels()[position_in_path - 1].as_deref();
+        let position = graph.position_of(&point.id());
+        match position.and_then(|position| graph.connection_for(previous_position, position, label)) {
+            Some(connection) => cost += graph.connections(previous_position)[connection].1,
+            None => return Err(NetErrors::MissingLink(previous_point.id().to_string(), point.id().to_string()))
         }
+        previous_position = position.expect("connected points are in the net");
     }
+
+    Ok(cost)
 }
 
 quick_error! {
@@ -93,6 +271,30 @@ quick_error! {
             description("Path cannot be built")
             display(r#"Path cannot be built: {}"#, path_error)
         }
+        MissingLink(from_id: String, to_id: String) {
+            description("Points are not connected in the net")
+            display(r#"The point with id "{}" is not connected to the point with id "{}""#, from_id, to_id)
+        }
+        DuplicatePoint(point_id: String) {
+            description("Point is repeated in the path")
+            display(r#"The point with id "{}" appears more than once in the path"#, point_id)
+        }
+        Stopped {
+            description("Search was stopped by its observer")
+            display(r#"Search was stopped by its observer before finding a path"#)
+        }
+        InvalidPageSize {
+            description("Page size must be at least one")
+            display(r#"A page must hold at least one path"#)
+        }
+        InvalidResource(resource: usize) {
+            description("Resource has no limit")
+            display(r#"The resource {} has no limit, so it cannot be minimized"#, resource)
+        }
+        InvalidCursor(cursor: String) {
+            description("Cursor does not continue a search in the net")
+            display(r#"The cursor "{}" does not continue a search in the net"#, cursor)
+        }
     }
 }
 
@@ -104,6 +306,7 @@ mod test {
     use node::Node;
     use path::Path;
     use node::NodeBuilder;
+    use path::PathBuilder;
 
     const A: char = 'A';
     const B: char = 'B';
@@ -203,7 +406,7 @@ mod test {
             Ok(_) => panic!("should throw an error"),
             Err(ref err) => {
                 match err {
-                    NetErrors::NoPathFound => assert!(true),
+                    NetErrors::NoPathFound => {},
                     _ => panic!("NoPathFound exception expected")
                 }
             }
@@ -227,7 +430,7 @@ mod test {
         };
 
         let paths = a_b_c_net.find_paths(&point_a, &point_c)
-            .expect(&format!("should not throw exception finding path a to c in net {:?}", a_b_c_net).into_boxed_str());
+            .unwrap_or_else(|_| panic!("should not throw exception finding path a to c in net {:?}", a_b_c_net));
 
         assert_eq!("A-B-C", format_list_of_paths(paths), "found path should be A-B-C");
     }
@@ -254,7 +457,7 @@ mod test {
         };
 
         let paths = triangle_net.find_paths(&point_a, &point_c)
-            .expect(&format!("should not throw exception finding path a to c in net {:?}", triangle_net).into_boxed_str());
+            .unwrap_or_else(|_| panic!("should not throw exception finding path a to c in net {:?}", triangle_net));
 
         let formatted_paths = format_list_of_paths(paths);
 
@@ -283,21 +486,360 @@ mod test {
         };
 
         let paths = triangle_net.find_paths(&point_a, &point_c)
-            .expect(&format!("should not throw exception finding path a to c in net {:?}", triangle_net).into_boxed_str());
+            .unwrap_or_else(|_| panic!("should not throw exception finding path a to c in net {:?}", triangle_net));
 
         let formatted_paths = format_list_of_paths(paths);
 
         assert_eq!(formatted_paths, "A-B-C + A-B-D-C + A-D-B-C + A-D-C", "should find the four feasible paths");
     }
 
+    // Given this net of points:
+    // A - B - C
+    //  \  |  /
+    //   \ | /
+    //     D
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_find_paths_should_find_the_same_paths_as_find_paths() {
+        let point_a = simple_point(A);
+        let point_b = simple_point(B);
+        let point_c = simple_point(C);
+        let point_d = simple_point(D);
+
+        let node_a = node_connected_to(point_a, vec![point_b, point_d]);
+        let node_b = node_connected_to(point_b, vec![point_a, point_c, point_d]);
+        let node_c = node_connected_to(point_c, vec![point_b, point_d]);
+        let node_d = node_connected_to(point_d, vec![point_a, point_c, point_b]);
+
+        let triangle_net: Net<SimplePoint> = Net {
+            nodes: vec![node_a, node_b, node_c, node_d]
+        };
+
+        let sequential_paths = triangle_net.find_paths(&point_a, &point_c).expect("should find paths sequentially");
+        let parallel_paths = triangle_net.par_find_paths(&point_a, &point_c).expect("should find paths in parallel");
+
+        assert_eq!(format_list_of_paths(parallel_paths), format_list_of_paths(sequential_paths));
+    }
+
+    // Given this net of points:
+    // A -2- B -3- C
+    #[test]
+    fn net_should_be_queried_by_point_id() {
+        let net = a_b_c_net_with_costs();
+
+        assert!(net.contains(&B));
+        assert!(!net.contains(&D));
+        assert_eq!(net.point(&C), Some(&simple_point(C)));
+        assert_eq!(net.point(&D), None);
+        assert_eq!(net.neighbors(&B).expect("B should be in the net"), vec![&simple_point(A), &simple_point(C)]);
+        assert!(net.neighbors(&D).is_err(), "D should not be in the net");
+    }
+
+    // Given this directed net of points:
+    // A -> B <-> C
+    //      ^
+    //      D
+    #[test]
+    fn net_should_know_the_degree_of_its_points() {
+        let point_a = simple_point(A);
+        let point_b = simple_point(B);
+        let point_c = simple_point(C);
+        let point_d = simple_point(D);
+
+        let net: Net<SimplePoint> = Net {
+            nodes: vec![
+                node(point_a, point_b),
+                node(point_b, point_c),
+                node(point_c, point_b),
+                node(point_d, point_b),
+            ]
+        };
+
+        let points: Vec<char> = net.iter_points().map(|point| point.id()).collect();
+        assert_eq!(points, vec![A, B, C, D]);
+        assert_eq!(net.in_degree(&B).unwrap(), 3);
+        assert_eq!(net.out_degree(&B).unwrap(), 1);
+        assert_eq!(net.in_degree(&D).unwrap(), 0);
+        assert!(net.out_degree(&'E').is_err(), "E should not be in the net");
+    }
+
+    // Given this directed net of points, with a train and a bus from A to B:
+    // A => B
+    #[test]
+    fn degrees_should_count_parallel_connections() {
+        let point_a = simple_point(A);
+        let point_b = simple_point(B);
+
+        let net: Net<SimplePoint> = Net {
+            nodes: vec![
+                NodeBuilder::new()
+                    .point(&point_a)
+                    .connected_point_with_label(&point_b, "train", 2)
+                    .connected_point_with_label(&point_b, "bus", 3)
+                    .build()
+                    .unwrap(),
+                non_connected_node(point_b),
+            ]
+        };
+
+        assert_eq!(net.out_degree(&A).unwrap(), 2);
+        assert_eq!(net.in_degree(&B).unwrap(), 2);
+    }
+
+    // Given this net of points:
+    // A -2- B -3- C
+    #[test]
+    fn find_paths_by_id_should_find_paths_between_ids() {
+        let net = a_b_c_net_with_costs();
+
+        let paths = net.find_paths_by_id(&A, &C).expect("should find paths from A to C");
+
+        assert_eq!(format_list_of_paths(paths), "A-B-C");
+        match net.find_paths_by_id(&A, &D) {
+            Err(NetErrors::PointNotFound(ref point)) => assert_eq!(point, "D"),
+            other => panic!("PointNotFound D expected, got {:?}", other)
+        }
+    }
+
+    // Given this net of points:
+    // A -2- B -3- C
+    #[test]
+    fn validate_path_should_return_the_cost_of_a_connected_path() {
+        let net = a_b_c_net_with_costs();
+        let path = path_of(vec![A, B, C]);
+
+        let cost = net.validate_path(&path).expect("A-B-C should be a valid path");
+
+        assert_eq!(cost, 5, "A-B-C should cost 2 + 3");
+    }
+
+    // Given this net of points:
+    // A -2- B -3- C
+    #[test]
+    fn validate_path_should_name_the_first_missing_link() {
+        let net = a_b_c_net_with_costs();
+        let path = path_of(vec![B, A, C]);
+
+        match net.validate_path(&path) {
+            Err(NetErrors::MissingLink(ref from, ref to)) => assert_eq!((from.as_str(), to.as_str()), ("A", "C")),
+            other => panic!("MissingLink A-C expected, got {:?}", other)
+        }
+    }
+
+    // Given this net of points:
+    // A -2- B -3- C
+    #[test]
+    fn validate_path_should_name_a_duplicate_point() {
+        let net = a_b_c_net_with_costs();
+        let path = path_of(vec![A, B, A]);
+
+        match net.validate_path(&path) {
+            Err(NetErrors::DuplicatePoint(ref point)) => assert_eq!(point, "A"),
+            other => panic!("DuplicatePoint A expected, got {:?}", other)
+        }
+    }
+
+    // Given this net of points:
+    // A - B - C
+    //  \     /
+    //   \   /
+    //     D
+    #[test]
+    fn find_paths_between_sets_should_find_paths_from_any_origin_to_any_destination() {
+        let point_a = simple_point(A);
+        let point_b = simple_point(B);
+        let point_c = simple_point(C);
+        let point_d = simple_point(D);
+
+        let node_a = node_connected_to(point_a, vec![point_b, point_d]);
+        let node_b = node_connected_to(point_b, vec![point_a, point_c]);
+        let node_c = node_connected_to(point_c, vec![point_b, point_d]);
+        let node_d = node_connected_to(point_d, vec![point_a, point_c]);
+
+        let triangle_net: Net<SimplePoint> = Net {
+            nodes: vec![node_a, node_b, node_c, node_d]
+        };
+
+        let paths = triangle_net.find_paths_between_sets(&[point_a, point_b], &[point_c, point_d])
+            .unwrap_or_else(|_| panic!("should not throw exception finding paths in net {:?}", triangle_net));
+
+        assert_eq!(format_list_of_paths(paths), "A-B-C + A-B-C-D + A-D + A-D-C + B-A-D + B-A-D-C + B-C + B-C-D");
+    }
+
+    // Given this net of points:
+    // A - B - C - D
+    #[test]
+    fn find_paths_between_sets_should_stop_at_the_destinations_the_same_way_for_any_count() {
+        let point_a = simple_point(A);
+        let point_b = simple_point(B);
+        let point_c = simple_point(C);
+        let point_d = simple_point(D);
+
+        let line_net: Net<SimplePoint> = Net {
+            nodes: vec![node(point_a, point_b), node(point_b, point_c), node(point_c, point_d), non_connected_node(point_d)]
+        };
+
+        let once = line_net.find_paths_between_sets(&[point_a], &[point_c]).expect("should find paths to C");
+        let twice = line_net.find_paths_between_sets(&[point_a], &[point_c, point_c]).expect("should find paths to C");
+        let both = line_net.find_paths_between_sets(&[point_a], &[point_c, point_d]).expect("should find paths to C and D");
+
+        assert_eq!(format_list_of_paths(twice), format_list_of_paths(once));
+        assert_eq!(format_list_of_paths(both), "A-B-C + A-B-C-D", "D is reached going through C");
+    }
+
+    // Given this net of points:
+    // A - B - C - D
+    #[test]
+    fn find_paths_between_sets_should_not_return_a_path_from_a_point_to_itself() {
+        let point_a = simple_point(A);
+        let point_b = simple_point(B);
+        let point_c = simple_point(C);
+        let point_d = simple_point(D);
+
+        let line_net: Net<SimplePoint> = Net {
+            nodes: vec![node(point_a, point_b), node(point_b, point_c), node(point_c, point_d), non_connected_node(point_d)]
+        };
+
+        let paths = line_net.find_paths_between_sets(&[point_a, point_b], &[point_b, point_c]).expect("should find paths to B and C");
+
+        assert_eq!(format_list_of_paths(paths), "A-B + A-B-C + B-C", "B is a destination only for the paths from A");
+    }
+
+    // Given this net of points, with a bus and a train from A to B:
+    // A =bus:3/train:2= B - C
+    #[test]
+    fn paths_should_record_the_parallel_connection_taken_at_each_hop() {
+        let point_a = simple_point(A);
+        let point_b = simple_point(B);
+        let point_c = simple_point(C);
+
+        let node_a = NodeBuilder::new()
+            .point(&point_a)
+            .connected_point_with_label(&point_b, "bus", 3)
+            .connected_point_with_label(&point_b, "train", 2)
+            .build()
+            .unwrap();
+        let net = Net {
+            nodes: vec![node_a, node(point_b, point_c), non_connected_node(point_c)]
+        };
+
+        let labels: Vec<Vec<Option<String>>> = net.find_paths(&point_a, &point_c)
+            .expect("should find paths from A to C")
+            .iter()
+            .map(|path| path.labels().to_vec())
+            .collect();
+        let shortest_path = net.shortest_path(&point_a, &point_c).expect("should find a path from A to C");
+
+        assert_eq!(labels, vec![
+            vec![Some(String::from("bus")), None],
+            vec![Some(String::from("train")), None],
+        ]);
+        assert_eq!(shortest_path.labels(), &[Some(String::from("train")), None]);
+        assert_eq!(net.validate_path(&shortest_path).unwrap(), 3);
+    }
+
+    // Given this net of points, with a bus and a train from A to B:
+    // A =3/2= B - C
+    #[test]
+    fn validate_path_should_cost_the_parallel_connection_taken() {
+        let point_a = simple_point(A);
+        let point_b = simple_point(B);
+        let point_c = simple_point(C);
+
+        let node_a = NodeBuilder::new()
+            .point(&point_a)
+            .connected_point_with_label(&point_b, "bus", 3)
+            .connected_point_with_label(&point_b, "train", 2)
+            .build()
+            .unwrap();
+        let net = Net {
+            nodes: vec![node_a, node(point_b, point_c), non_connected_node(point_c)]
+        };
+        let path_by = |label: &str| PathBuilder::new()
+            .points(vec![point_a, point_b, point_c])
+            .labels(vec![Some(String::from(label)), None])
+            .build()
+            .unwrap();
+
+        assert_eq!(net.validate_path(&path_by("bus")).unwrap(), 4);
+        assert_eq!(net.validate_path(&path_by("train")).unwrap(), 3);
+        match net.validate_path(&path_by("plane")) {
+            Err(NetErrors::MissingLink(ref from, ref to)) => assert_eq!((from.as_str(), to.as_str()), ("A", "B")),
+            other => panic!("MissingLink A-B expected, got {:?}", other)
+        }
+    }
+
+    // Given this net of points:
+    // 0 - 1 - 2 - ... - 4999
+    #[test]
+    fn find_paths_should_not_overflow_the_stack_in_long_chains() {
+        const CHAIN_LENGTH: u32 = 5_000;
+
+        #[derive(Clone, Debug)]
+        struct ChainPoint {
+            position: u32
+        }
+
+        impl Point for ChainPoint {
+            type Identifier = u32;
+
+            fn id(&self) -> u32 {
+                self.position
+            }
+        }
+
+        let chain_point = |position: u32| ChainPoint { position };
+        let chain_net: Net<ChainPoint> = Net {
+            nodes: (0..CHAIN_LENGTH)
+                .map(|position| {
+                    let mut builder = NodeBuilder::new();
+                    builder.point(&chain_point(position));
+                    if position > 0 {
+                        builder.connected_point(&chain_point(position - 1));
+                    }
+                    if position < CHAIN_LENGTH - 1 {
+                        builder.connected_point(&chain_point(position + 1));
+                    }
+                    builder.build().unwrap()
+                })
+                .collect()
+        };
+
+        let paths = chain_net.find_paths(&chain_point(0), &chain_point(CHAIN_LENGTH - 1))
+            .expect("should find the path along the chain");
+
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].hops(), (CHAIN_LENGTH - 1) as usize);
+    }
+
+    fn a_b_c_net_with_costs() -> Net<SimplePoint> {
+        let point_a = simple_point(A);
+        let point_b = simple_point(B);
+        let point_c = simple_point(C);
+
+        Net {
+            nodes: vec![
+                NodeBuilder::new().point(&point_a).connected_point_with_cost(&point_b, 2).build().unwrap(),
+                NodeBuilder::new().point(&point_b).connected_point_with_cost(&point_a, 2).connected_point_with_cost(&point_c, 3).build().unwrap(),
+                NodeBuilder::new().point(&point_c).connected_point_with_cost(&point_b, 3).build().unwrap(),
+            ]
+        }
+    }
+
+    fn path_of(names: Vec<char>) -> Path<SimplePoint> {
+        PathBuilder::new()
+            .points(names.into_iter().map(simple_point).collect())
+            .build()
+            .unwrap()
+    }
 
     fn format_path_kebab(path: &Path<SimplePoint>) -> String {
-        return format!("{}", path);
+        format!("{}", path)
     }
 
     fn format_list_of_paths(paths: Vec<Path<SimplePoint>>) -> String {
         let mut formatted_and_ordered_paths: Vec<String> = paths.iter()
-            .map(|path| format_path_kebab(path))
+            .map(format_path_kebab)
             .collect();
 
         formatted_and_ordered_paths.sort();
@@ -306,7 +848,7 @@ mod test {
     }
 
     fn simple_point(name: char) -> SimplePoint {
-        SimplePoint { name: name.clone() }
+        SimplePoint { name }
     }
 
     fn node(from: SimplePoint, to: SimplePoint) -> Node<SimplePoint> {
@@ -1,39 +1,174 @@
 use path::Path;
+use timetable::Timetable;
+
+use std::hash::Hash;
+
+pub type Cost = u64;
+
+pub const DEFAULT_COST: Cost = 1;
 
 pub trait Point: Clone {
-    type Identifier: PartialEq + ToString;
+    /// Identifies the point in the net, where points are indexed by their identifier.
+    ///
+    /// Requiring `Eq + Hash` instead of `PartialEq` breaks the points identified by
+    /// a type without them, such as `f64`.
+    type Identifier: Eq + Hash + ToString;
 
     fn id(&self) -> Self::Identifier;
 
     fn is(&self, other_point: &Self) -> bool {
-        &self.id() == &other_point.id()
+        self.id() == other_point.id()
     }
 }
 
-#[derive(Debug)]
-struct Connection<T: Point> {
-    pub to: T
+#[derive(Debug, Clone)]
+struct Connection<T: Point, P> {
+    pub to: T,
+    pub cost: Cost,
+    pub payload: P,
 }
 
-impl<T: Point> Connection<T> {
+impl<T: Point, P> Connection<T, P> {
+    fn with_payload(to: T, cost: Cost, payload: P) -> Connection<T, P> {
+        Connection { to, cost, payload }
+    }
+
     pub fn is_connected_to(&self, point: &T) -> bool {
         self.to.is(point)
     }
 }
 
-impl<T: Point> PartialEq for Connection<T> {
-    fn eq(&self, other_connection: &Connection<T>) -> bool {
-        self.to.is(&other_connection.to)
+/// Values only some connections have, by the index of their connection,
+/// so the connections without them do not pay for them.
+#[derive(Debug, Clone)]
+pub(crate) struct SideTable<V> {
+    entries: Vec<(usize, V)>,
+}
+
+impl<V> Default for SideTable<V> {
+    fn default() -> Self {
+        SideTable { entries: Vec::new() }
+    }
+}
+
+impl<V> SideTable<V> {
+    pub fn get(&self, connection: usize) -> Option<&V> {
+        self.entries.binary_search_by_key(&connection, |&(entry_connection, _)| entry_connection)
+            .ok()
+            .map(|entry| &self.entries[entry].1)
+    }
+
+    pub fn insert(&mut self, connection: usize, value: V) {
+        match self.entries.binary_search_by_key(&connection, |&(entry_connection, _)| entry_connection) {
+            Ok(entry) => self.entries[entry].1 = value,
+            Err(entry) => self.entries.insert(entry, (connection, value))
+        }
+    }
+
+    pub fn remove(&mut self, connection: usize) {
+        if let Ok(entry) = self.entries.binary_search_by_key(&connection, |&(entry_connection, _)| entry_connection) {
+            self.entries.remove(entry);
+        }
+    }
+}
+
+/// Labels, timetables and resources of the connections of a node that were given them.
+#[derive(Debug, Clone, Default)]
+struct SideTables {
+    labels: SideTable<String>,
+    timetables: SideTable<Timetable>,
+    resources: SideTable<Vec<Cost>>,
+}
+
+/// Everything a connection of a node can be given, to add a connection needing more than one
+/// of the `connected_point_with_*` builders, such as a labeled line with its own timetable.
+#[derive(Debug, Clone)]
+pub struct ConnectionBuilder<P = ()> {
+    cost: Cost,
+    label: Option<String>,
+    timetable: Option<Timetable>,
+    resources: Option<Vec<Cost>>,
+    payload: P,
+}
+
+impl ConnectionBuilder {
+    pub fn new(cost: Cost) -> ConnectionBuilder {
+        ConnectionBuilder::with_payload(cost, ())
+    }
+}
+
+impl<P> ConnectionBuilder<P> {
+    pub fn with_payload(cost: Cost, payload: P) -> ConnectionBuilder<P> {
+        ConnectionBuilder { cost, label: None, timetable: None, resources: None, payload }
+    }
+
+    pub fn label(&mut self, label: &str) -> &mut Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    pub fn timetable(&mut self, timetable: &Timetable) -> &mut Self {
+        self.timetable = Some(timetable.clone());
+        self
+    }
+
+    pub fn resources(&mut self, resources: &[Cost]) -> &mut Self {
+        self.resources = Some(resources.to_vec());
+        self
     }
 }
 
+/// Point of a net with its connections, each one carrying a payload of type `P`.
 #[derive(Debug)]
-pub struct Node<T: Point> {
+pub struct Node<T: Point, P = ()> {
     point: T,
-    connections: Vec<Connection<T>>,
+    connections: Vec<Connection<T, P>>,
+    side_tables: SideTables,
 }
 
-impl<T: Point> Node<T> {
+impl<T: Point, P> Node<T, P> {
+    pub fn point(&self) -> &T {
+        &self.point
+    }
+
+    pub fn neighbors(&self) -> impl Iterator<Item=&T> {
+        self.connections.iter()
+            .map(|conn| &conn.to)
+    }
+
+    pub fn degree(&self) -> usize {
+        self.connections.len()
+    }
+
+    pub(crate) fn connections(&self) -> impl Iterator<Item=(&T, Cost, Option<&str>, &P)> {
+        self.connections.iter()
+            .enumerate()
+            .map(move |(connection, conn)| (&conn.to, conn.cost, self.label(connection), &conn.payload))
+    }
+
+    pub fn payloads(&self) -> impl Iterator<Item=(&T, &P)> {
+        self.connections.iter()
+            .map(|conn| (&conn.to, &conn.payload))
+    }
+
+    /// Label of a connection, by its index in the connections of the node.
+    pub(crate) fn label(&self, connection: usize) -> Option<&str> {
+        self.side_tables.labels.get(connection).map(String::as_str)
+    }
+
+    pub(crate) fn timetable(&self, connection: usize) -> Option<&Timetable> {
+        self.side_tables.timetables.get(connection)
+    }
+
+    pub(crate) fn payload(&self, connection: usize) -> &P {
+        &self.connections[connection].payload
+    }
+
+    /// Resources a connection uses, empty if it was not given any.
+    pub(crate) fn resources(&self, connection: usize) -> &[Cost] {
+        self.side_tables.resources.get(connection).map_or(&[], |resources| &resources[..])
+    }
+
     pub fn point_is(&self, point: &T) -> bool {
         self.point.is(point)
     }
@@ -43,6 +178,44 @@ impl<T: Point> Node<T> {
             .any(|conn| conn.is_connected_to(point))
     }
 
+    /// Cost of the cheapest connection to the point.
+    pub fn cost_to(&self, point: &T) -> Option<Cost> {
+        self.connections.iter()
+            .filter(|conn| conn.is_connected_to(point))
+            .map(|conn| conn.cost)
+            .min()
+    }
+
+    /// Payload of the cheapest connection to the point.
+    pub fn payload_to(&self, point: &T) -> Option<&P> {
+        self.connections.iter()
+            .filter(|conn| conn.is_connected_to(point))
+            .min_by_key(|conn| conn.cost)
+            .map(|conn| &conn.payload)
+    }
+
+    /// Cost and payload of the connection to the point with the label, or of the cheapest
+    /// one when there is no label, as paths record the connection taken at each hop.
+    pub(crate) fn connection_to(&self, point: &T, label: Option<&str>) -> Option<(Cost, &P)> {
+        let mut connections = self.connections.iter()
+            .enumerate()
+            .filter(|(_, conn)| conn.is_connected_to(point));
+        let connection = match label {
+            Some(_) => connections.find(|&(connection, _)| self.label(connection) == label),
+            None => connections.min_by_key(|(_, conn)| conn.cost)
+        };
+        connection.map(|(_, conn)| (conn.cost, &conn.payload))
+    }
+
+    /// Labels of the connections to the point that have one.
+    pub fn labels_to(&self, point: &T) -> Vec<&str> {
+        self.connections.iter()
+            .enumerate()
+            .filter(|(_, conn)| conn.is_connected_to(point))
+            .filter_map(|(connection, _)| self.label(connection))
+            .collect()
+    }
+
     pub fn connected_points_not_in_path(&self, path: &Path<T>) -> Option<Vec<&T>> {
         let points: Vec<&T> = self.connections.iter()
             .filter(|connection| path.do_not_contains(&connection.to))
@@ -57,50 +230,84 @@ impl<T: Point> Node<T> {
     }
 }
 
-impl<T: Point> PartialEq for Node<T> {
-    fn eq(&self, other_node: &Node<T>) -> bool {
-        if !self.point.is(&other_node.point) {
+/// Connections to the same point are parallel ones unless they have the same label.
+fn is_same_connection<T: Point, P>(connection: &Connection<T, P>, label: Option<&str>, other_connection: &Connection<T, P>, other_label: Option<&str>) -> bool {
+    connection.is_connected_to(&other_connection.to) && label == other_label
+}
+
+impl<T: Point, P> PartialEq for Node<T, P> {
+    fn eq(&self, other_node: &Node<T, P>) -> bool {
+        if !self.point.is(&other_node.point) || self.degree() != other_node.degree() {
             return false;
         }
 
-        self.connections == other_node.connections
+        self.connections.iter()
+            .zip(&other_node.connections)
+            .enumerate()
+            .all(|(connection, (conn, other_conn))| is_same_connection(conn, self.label(connection), other_conn, other_node.label(connection)))
     }
 }
 
 #[derive(Debug)]
-pub struct NodeBuilder<T: Point> {
+pub struct NodeBuilder<T: Point, P = ()> {
     point: Option<T>,
-    connected_points: Option<Vec<T>>,
+    connected_points: Option<Vec<Connection<T, P>>>,
+    side_tables: SideTables,
+}
+
+/// Builders with payloads are created with `default`, as `new` is only for the ones without.
+impl<T: Point, P> Default for NodeBuilder<T, P> {
+    fn default() -> Self {
+        NodeBuilder { point: None, connected_points: None, side_tables: SideTables::default() }
+    }
 }
 
 impl<T: Point> NodeBuilder<T> {
     pub fn new() -> NodeBuilder<T> {
         let point = None;
         let connections = None;
-        NodeBuilder { point, connected_points: connections }
+        NodeBuilder { point, connected_points: connections, side_tables: SideTables::default() }
     }
+}
 
-    pub fn point(&mut self, point: &T) -> &mut Self {
-        let point_to_add = point.clone();
-        self.point = Some(point_to_add);
+impl<T: Point, P: Clone + Default> NodeBuilder<T, P> {
+    pub fn connected_point(&mut self, point: &T) -> &mut Self {
+        self.connected_point_with_cost(point, DEFAULT_COST)
+    }
 
+    pub fn connected_point_with_cost(&mut self, point: &T, cost: Cost) -> &mut Self {
+        self.connection(point, ConnectionBuilder::with_payload(cost, P::default()));
         self
     }
 
-    pub fn connected_point(&mut self, point: &T) -> &mut Self {
-        if self.node_is_connected_to(point) {
-            return self;
-        }
+    /// Connects the point through a connection only available at the departures of the
+    /// timetable. Its cost is the duration of the quickest departure.
+    pub fn connected_point_with_timetable(&mut self, point: &T, timetable: &Timetable) -> &mut Self {
+        let mut connection = ConnectionBuilder::with_payload(timetable.shortest_duration().unwrap_or(DEFAULT_COST), P::default());
+        connection.timetable(timetable);
+        self.connection(point, connection);
+        self
+    }
 
-        let point_connected = point.clone();
-        match self.connected_points {
-            Some(ref mut c) => c.push(point_connected),
-            None => self.connected_points = Some(vec![point_connected])
-        }
+    /// Adds a connection to the point told apart from other connections to it by its label,
+    /// such as the bus and the train between the same two stops.
+    pub fn connected_point_with_label(&mut self, point: &T, label: &str, cost: Cost) -> &mut Self {
+        let mut connection = ConnectionBuilder::with_payload(cost, P::default());
+        connection.label(label);
+        self.connection(point, connection);
+        self
+    }
 
+    /// Connects the point through a connection that uses the given amount of each resource.
+    pub fn connected_point_with_resources(&mut self, point: &T, cost: Cost, resources: &[Cost]) -> &mut Self {
+        let mut connection = ConnectionBuilder::with_payload(cost, P::default());
+        connection.resources(resources);
+        self.connection(point, connection);
         self
     }
 
+    // Keeps taking a `Vec`, as it always has, so the signature of the builder does not change.
+    #[allow(clippy::ptr_arg)]
     pub fn connected_points(&mut self, connected_points: &Vec<T>) -> &mut Self {
         connected_points.iter()
             .for_each(|connected_to| {
@@ -109,8 +316,29 @@ impl<T: Point> NodeBuilder<T> {
 
         self
     }
+}
 
-    pub fn build(&self) -> Result<Node<T>, String> {
+impl<T: Point, P: Clone> NodeBuilder<T, P> {
+    pub fn point(&mut self, point: &T) -> &mut Self {
+        let point_to_add = point.clone();
+        self.point = Some(point_to_add);
+
+        self
+    }
+
+    /// Adds a connection to the point carrying the given payload.
+    pub fn connected_point_with_payload(&mut self, point: &T, cost: Cost, payload: P) -> &mut Self {
+        self.connection(point, ConnectionBuilder::with_payload(cost, payload));
+        self
+    }
+
+    /// Adds a connection to the point with the cost, label, timetable, resources and payload given.
+    pub fn connected_point_through(&mut self, point: &T, connection: &ConnectionBuilder<P>) -> &mut Self {
+        self.connection(point, connection.clone());
+        self
+    }
+
+    pub fn build(&self) -> Result<Node<T, P>, String> {
         if self.point.is_none() {
             return Err(String::from("Should specify a point"));
         }
@@ -124,28 +352,60 @@ impl<T: Point> NodeBuilder<T> {
             .unwrap()
             .clone();
 
-        let to_connection = |connected_point: &T| Connection {
-            to: connected_point.clone()
-        };
-
         let connections = self.connected_points
-            .as_ref()
-            .unwrap_or(&Vec::new())
-            .iter()
-            .map(to_connection)
-            .collect();
+            .clone()
+            .unwrap_or_default();
 
         Ok(Node {
             point,
             connections,
+            side_tables: self.side_tables.clone(),
         })
     }
 
+    /// Adds the connection, replacing the one to the same point with the same label if there
+    /// is already one, so the connection keeps the data it was given last.
+    fn connection(&mut self, point: &T, connection: ConnectionBuilder<P>) {
+        let connections = self.connected_points.get_or_insert_with(Vec::new);
+        let side_tables = &mut self.side_tables;
+        let label = connection.label.as_deref();
+        let added = Connection::with_payload(point.clone(), connection.cost, connection.payload);
+        let repeated = connections.iter()
+            .enumerate()
+            .position(|(index, existing)| is_same_connection(existing, side_tables.labels.get(index).map(String::as_str), &added, label));
+
+        let index = match repeated {
+            Some(index) => {
+                connections[index] = added;
+                index
+            },
+            None => {
+                connections.push(added);
+                connections.len() - 1
+            }
+        };
+        if let Some(label) = connection.label {
+            side_tables.labels.insert(index, label);
+        }
+        match connection.timetable {
+            Some(timetable) => side_tables.timetables.insert(index, timetable),
+            None => side_tables.timetables.remove(index)
+        }
+        match connection.resources {
+            Some(resources) => side_tables.resources.insert(index, resources),
+            None => side_tables.resources.remove(index)
+        }
+    }
+
+    pub(crate) fn built_point(&self) -> Option<&T> {
+        self.point.as_ref()
+    }
+
     fn node_is_connected_to(&self, point: &T) -> bool {
         match self.connected_points {
             None => false,
             Some(ref connections) => connections.iter()
-                .any(|connected_point| connected_point.is(point))
+                .any(|connection| connection.is_connected_to(point))
         }
     }
 }
@@ -178,12 +438,13 @@ mod test {
         let iceland = get_country(ICELAND);
         let austria = get_country(AUSTRIA);
 
-        let iceland_node = Node {
+        let iceland_node: Node<Country> = Node {
             point: iceland,
             connections: Vec::new(),
+            side_tables: SideTables::default(),
         };
 
-        assert_eq!(iceland_node.is_connected_to(&austria), false);
+        assert!(!iceland_node.is_connected_to(&austria));
     }
 
     #[test]
@@ -191,14 +452,13 @@ mod test {
         let portugal = get_country(PORTUGAL);
         let spain = get_country(SPAIN);
 
-        let portugal_node = Node {
+        let portugal_node: Node<Country> = Node {
             point: portugal,
-            connections: vec![Connection {
-                to: spain.clone()
-            }],
+            connections: vec![Connection::with_payload(spain.clone(), DEFAULT_COST, ())],
+            side_tables: SideTables::default(),
         };
 
-        assert_eq!(portugal_node.is_connected_to(&spain), true);
+        assert!(portugal_node.is_connected_to(&spain));
     }
 
     #[test]
@@ -206,18 +466,16 @@ mod test {
         let portugal = get_country(PORTUGAL);
         let spain = get_country(SPAIN);
 
-        let portugal_node = Node {
+        let portugal_node: Node<Country> = Node {
             point: portugal.clone(),
-            connections: vec![Connection {
-                to: spain.clone()
-            }],
+            connections: vec![Connection::with_payload(spain.clone(), DEFAULT_COST, ())],
+            side_tables: SideTables::default(),
         };
 
         let other_portugal_node = Node {
             point: portugal.clone(),
-            connections: vec![Connection {
-                to: spain.clone()
-            }],
+            connections: vec![Connection::with_payload(spain.clone(), DEFAULT_COST, ())],
+            side_tables: SideTables::default(),
         };
 
         assert_eq!(portugal_node, other_portugal_node);
@@ -236,9 +494,8 @@ mod test {
 
         let expected_portugal_node = Node {
             point: portugal,
-            connections: vec![Connection {
-                to: spain.clone()
-            }],
+            connections: vec![Connection::with_payload(spain.clone(), DEFAULT_COST, ())],
+            side_tables: SideTables::default(),
         };
 
         assert_eq!(portugal_node, expected_portugal_node);
@@ -261,19 +518,117 @@ mod test {
         let expected_spain_node = Node {
             point: spain,
             connections: vec![
-                Connection {
-                    to: portugal.clone()
-                },
-                Connection {
-                    to: france.clone()
-                }
+                Connection::with_payload(portugal.clone(), DEFAULT_COST, ()),
+                Connection::with_payload(france.clone(), DEFAULT_COST, ())
             ],
+            side_tables: SideTables::default(),
         };
 
         print!("pero qué me estás contando {:?}", spain_node);
         assert_eq!(spain_node, expected_spain_node, "Spain should be connected once to Portugal and France");
     }
 
+    #[test]
+    fn builder_should_keep_the_cost_of_each_connection() {
+        let portugal = get_country(PORTUGAL);
+        let spain = get_country(SPAIN);
+        let france = get_country(FRANCE);
+
+        let spain_node = NodeBuilder::new()
+            .point(&spain)
+            .connected_point_with_cost(&portugal, 3)
+            .connected_point(&france)
+            .build()
+            .expect("should build spain node");
+
+        assert_eq!(spain_node.cost_to(&portugal), Some(3));
+        assert_eq!(spain_node.cost_to(&france), Some(DEFAULT_COST));
+        assert_eq!(spain_node.cost_to(&spain), None);
+    }
+
+    #[test]
+    fn builder_should_keep_parallel_connections_with_different_labels() {
+        let portugal = get_country(PORTUGAL);
+        let spain = get_country(SPAIN);
+
+        let spain_node = NodeBuilder::new()
+            .point(&spain)
+            .connected_point_with_label(&portugal, "train", 3)
+            .connected_point_with_label(&portugal, "plane", 2)
+            .connected_point_with_label(&portugal, "train", 5)
+            .build()
+            .expect("should build spain node");
+
+        assert_eq!(spain_node.degree(), 2, "the second train should replace the first one");
+        assert_eq!(spain_node.labels_to(&portugal), vec!["train", "plane"]);
+        assert_eq!(spain_node.connection_to(&portugal, Some("train")), Some((5, &())));
+        assert_eq!(spain_node.cost_to(&portugal), Some(2));
+    }
+
+    #[test]
+    fn builder_should_keep_side_data_only_for_the_connections_given_it() {
+        let portugal = get_country(PORTUGAL);
+        let spain = get_country(SPAIN);
+        let france = get_country(FRANCE);
+        let mut timetable = Timetable::new();
+        timetable.departure(10, 4);
+
+        let spain_node: Node<Country> = NodeBuilder::new()
+            .point(&spain)
+            .connected_point(&portugal)
+            .connected_point_with_timetable(&france, &timetable)
+            .connected_point_with_resources(&portugal, 2, &[7])
+            .connected_point_with_label(&france, "train", 3)
+            .build()
+            .expect("should build spain node");
+
+        assert_eq!(spain_node.degree(), 3, "the connection with resources replaces the first one");
+        assert_eq!((spain_node.timetable(0), spain_node.timetable(1)), (None, Some(&timetable)));
+        assert_eq!((spain_node.cost_to(&portugal), spain_node.resources(0)), (Some(2), &[7][..]));
+        assert!(spain_node.resources(1).is_empty());
+        assert_eq!((spain_node.label(1), spain_node.label(2)), (None, Some("train")));
+    }
+
+    #[test]
+    fn builder_should_give_labeled_connections_their_own_data() {
+        let portugal = get_country(PORTUGAL);
+        let spain = get_country(SPAIN);
+        let mut timetable = Timetable::new();
+        timetable.departure(10, 4);
+
+        let spain_node = NodeBuilder::default()
+            .point(&spain)
+            .connected_point_through(&portugal, ConnectionBuilder::with_payload(4, "coach").label("bus").timetable(&timetable))
+            .connected_point_through(&portugal, ConnectionBuilder::with_payload(3, "high speed").label("train").resources(&[2]))
+            .connected_point_through(&portugal, ConnectionBuilder::with_payload(6, "regional").label("train"))
+            .build()
+            .expect("should build spain node");
+
+        assert_eq!(spain_node.labels_to(&portugal), vec!["bus", "train"]);
+        assert_eq!(spain_node.connection_to(&portugal, Some("bus")), Some((4, &"coach")));
+        assert_eq!(spain_node.connection_to(&portugal, Some("train")), Some((6, &"regional")), "the second train replaces the first one");
+        assert_eq!((spain_node.timetable(0), spain_node.timetable(1)), (Some(&timetable), None));
+        assert!(spain_node.resources(1).is_empty(), "the second train was given no resources");
+    }
+
+    #[test]
+    fn node_should_expose_its_point_and_neighbors() {
+        let portugal = get_country(PORTUGAL);
+        let spain = get_country(SPAIN);
+        let france = get_country(FRANCE);
+
+        let spain_node = NodeBuilder::new()
+            .point(&spain)
+            .connected_points(&vec![portugal.clone(), france.clone()])
+            .build()
+            .expect("should build spain node");
+
+        let neighbors: Vec<String> = spain_node.neighbors().map(|country| country.id()).collect();
+        assert_eq!(spain_node.point().id(), SPAIN);
+        assert_eq!(neighbors, vec![PORTUGAL, FRANCE]);
+        assert_eq!(spain_node.degree(), 2);
+    }
+
     #[test]
     fn builder_should_fail_if_there_is_no_point() {
         let country_node_builder: NodeBuilder<Country> = NodeBuilder::new();
@@ -0,0 +1,193 @@
+use node::Point;
+use path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchControl {
+    Continue,
+    Stop,
+}
+
+/// Receives what a search does while it runs, with the payloads of type `P` of the connections
+/// involved. Any callback can stop the search returning `SearchControl::Stop`, and the search
+/// then returns the paths found so far, failing with `NetErrors::Stopped` if it found none.
+///
+/// Only the depth first search of `find_paths`, the Dijkstra searches of `shortest_path` and
+/// `shortest_paths_per_pair` and the bidirectional search are observed, by their `_observed` and
+/// `_with_stats` methods. A*, all pairs, timetable, resource constrained, Pareto and turn searches
+/// take no observer.
+pub trait SearchObserver<T: Point, P = ()> {
+    /// The search is about to follow the connections of a point `depth` hops away from the origin,
+    /// which it reached through a connection carrying `arrived_through`, or none at the origin.
+    fn on_expand(&mut self, _point: &T, _depth: usize, _arrived_through: Option<&P>) -> SearchControl {
+        SearchControl::Continue
+    }
+
+    /// A connection carrying `payload` is not followed because its point was already visited.
+    fn on_prune(&mut self, _from: &T, _to: &T, _payload: &P) -> SearchControl {
+        SearchControl::Continue
+    }
+
+    fn on_path_found(&mut self, _path: &Path<T>) -> SearchControl {
+        SearchControl::Continue
+    }
+}
+
+/// Observer used by the searches that nobody is watching.
+pub struct NoObserver;
+
+impl<T: Point, P> SearchObserver<T, P> for NoObserver {}
+
+#[cfg(test)]
+mod test {
+    use net::Net;
+    use node::Node;
+    use node::NodeBuilder;
+    use observer::*;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    struct RecordingObserver {
+        events: Vec<String>,
+        stop_after_paths: usize,
+    }
+
+    impl SearchObserver<SimplePoint> for RecordingObserver {
+        fn on_expand(&mut self, point: &SimplePoint, depth: usize, _arrived_through: Option<&()>) -> SearchControl {
+            self.events.push(format!("expand {} at {}", point.name, depth));
+            SearchControl::Continue
+        }
+
+        fn on_prune(&mut self, from: &SimplePoint, to: &SimplePoint, _payload: &()) -> SearchControl {
+            self.events.push(format!("prune {}-{}", from.name, to.name));
+            SearchControl::Continue
+        }
+
+        fn on_path_found(&mut self, path: &Path<SimplePoint>) -> SearchControl {
+            self.events.push(format!("found {}", path));
+            self.stop_after_paths -= 1;
+            if self.stop_after_paths == 0 {
+                SearchControl::Stop
+            } else {
+                SearchControl::Continue
+            }
+        }
+    }
+
+    // Given this net of points:
+    // A - B - C
+    //  \     /
+    //   \   /
+    //     D
+    fn triangle_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', "BD"),
+                node('B', "AC"),
+                node('C', "BD"),
+                node('D', "AC"),
+            ]
+        }
+    }
+
+    #[test]
+    fn observer_should_see_expansions_prunes_and_found_paths() {
+        let net = triangle_net();
+        let mut observer = RecordingObserver { events: Vec::new(), stop_after_paths: 10 };
+
+        net.find_paths_observed(&simple_point('A'), &simple_point('C'), &mut observer)
+            .expect("should find paths from A to C");
+
+        assert_eq!(observer.events, vec![
+            "expand A at 0",
+            "expand B at 1", "prune B-A", "found A-B-C",
+            "expand D at 1", "prune D-A", "found A-D-C",
+        ]);
+    }
+
+    #[test]
+    fn observer_should_stop_the_search_early() {
+        let net = triangle_net();
+        let mut observer = RecordingObserver { events: Vec::new(), stop_after_paths: 1 };
+
+        let paths = net.find_paths_observed(&simple_point('A'), &simple_point('C'), &mut observer)
+            .expect("should return the path found before stopping");
+
+        assert_eq!(paths.len(), 1);
+        assert_eq!(observer.events.last().unwrap(), "found A-B-C");
+    }
+
+    /// Records the payloads of the connections each event involves.
+    struct PayloadObserver {
+        events: Vec<String>,
+    }
+
+    impl SearchObserver<SimplePoint, &'static str> for PayloadObserver {
+        fn on_expand(&mut self, point: &SimplePoint, _depth: usize, arrived_through: Option<&&'static str>) -> SearchControl {
+            self.events.push(format!("expand {} through {}", point.name, arrived_through.unwrap_or(&"nothing")));
+            SearchControl::Continue
+        }
+
+        fn on_prune(&mut self, from: &SimplePoint, to: &SimplePoint, payload: &&'static str) -> SearchControl {
+            self.events.push(format!("prune {}-{} by {}", from.name, to.name, payload));
+            SearchControl::Continue
+        }
+    }
+
+    // Given this net of points, with the road of each connection:
+    // A -ab- B -bc- C
+    //  <-ba-
+    fn roads_net() -> Net<SimplePoint, &'static str> {
+        let (a, b, c) = (simple_point('A'), simple_point('B'), simple_point('C'));
+        Net {
+            nodes: vec![
+                NodeBuilder::default().point(&a).connected_point_with_payload(&b, 1, "ab").build().unwrap(),
+                NodeBuilder::default()
+                    .point(&b)
+                    .connected_point_with_payload(&a, 1, "ba")
+                    .connected_point_with_payload(&c, 1, "bc")
+                    .build()
+                    .unwrap(),
+                NodeBuilder::default().point(&c).build().unwrap(),
+            ]
+        }
+    }
+
+    #[test]
+    fn observer_should_see_the_payloads_of_the_connections_involved() {
+        let net = roads_net();
+        let (a, c) = (simple_point('A'), simple_point('C'));
+        let mut depth_first = PayloadObserver { events: Vec::new() };
+        let mut cheapest_first = PayloadObserver { events: Vec::new() };
+
+        net.find_paths_observed(&a, &c, &mut depth_first).expect("should find paths from A to C");
+        net.shortest_path_observed(&a, &c, &mut cheapest_first).expect("should find a path from A to C");
+
+        let expected = vec!["expand A through nothing", "expand B through ab", "prune B-A by ba"];
+        assert_eq!(depth_first.events, expected);
+        assert_eq!(cheapest_first.events, expected);
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connected_to: &str) -> Node<SimplePoint> {
+        let connected_points: Vec<SimplePoint> = connected_to.chars().map(simple_point).collect();
+        NodeBuilder::new()
+            .point(&simple_point(name))
+            .connected_points(&connected_points)
+            .build()
+            .unwrap()
+    }
+}
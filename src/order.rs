@@ -0,0 +1,204 @@
+use compact::CompactNet;
+use index::NetIndex;
+use index::Topology;
+use net;
+use net::Net;
+use net::NetErrors;
+use node::Cost;
+use node::Point;
+use observer::NoObserver;
+use path::Path;
+use std::slice;
+
+/// Order of the paths returned by a search. In the sorted orders, paths that tie are ordered
+/// by the ids of their points and then by the labels of their connections, so those orders
+/// never depend on how the connections were added.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathOrder {
+    /// Order in which the depth first search finds them, which follows the order the
+    /// connections were added in.
+    Discovery,
+    Hops,
+    /// Cheapest first, and then fewest hops.
+    Cost,
+    Lexicographic,
+}
+
+impl<T: Point, P> Net<T, P> where T::Identifier: Ord {
+    pub fn find_paths_ordered(&self, origin: &T, destination: &T, order: PathOrder) -> Result<Vec<Path<T>>, NetErrors> {
+        find_paths_ordered(&NetIndex::new(self), slice::from_ref(origin), slice::from_ref(destination), order)
+    }
+
+    pub fn find_paths_between_sets_ordered(&self, origins: &[T], destinations: &[T], order: PathOrder) -> Result<Vec<Path<T>>, NetErrors> {
+        find_paths_ordered(&NetIndex::new(self), origins, destinations, order)
+    }
+}
+
+impl<T: Point, P> CompactNet<T, P> where T::Identifier: Ord {
+    pub fn find_paths_ordered(&self, origin: &T, destination: &T, order: PathOrder) -> Result<Vec<Path<T>>, NetErrors> {
+        find_paths_ordered(self, slice::from_ref(origin), slice::from_ref(destination), order)
+    }
+
+    pub fn find_paths_between_sets_ordered(&self, origins: &[T], destinations: &[T], order: PathOrder) -> Result<Vec<Path<T>>, NetErrors> {
+        find_paths_ordered(self, origins, destinations, order)
+    }
+}
+
+fn find_paths_ordered<T, G>(graph: &G, origins: &[T], destinations: &[T], order: PathOrder) -> Result<Vec<Path<T>>, NetErrors>
+    where T: Point, T::Identifier: Ord, G: Topology<T> {
+    let paths = net::find_paths_between_sets(graph, origins, destinations, &mut NoObserver)?;
+    sort_paths(graph, paths, order)
+}
+
+/// Sorts the paths in the given order, failing with the error of the first path that is not
+/// valid in the graph when they are sorted by cost.
+pub(crate) fn sort_paths<T, G>(graph: &G, mut paths: Vec<Path<T>>, order: PathOrder) -> Result<Vec<Path<T>>, NetErrors>
+    where T: Point, T::Identifier: Ord, G: Topology<T> {
+    match order {
+        PathOrder::Discovery => {},
+        PathOrder::Hops => paths.sort_by_cached_key(|path| (path.hops(), ids(path), labels(path))),
+        PathOrder::Cost => {
+            let mut costed_paths = paths.into_iter()
+                .map(|path| Ok((net::validate_path(graph, &path)?, path)))
+                .collect::<Result<Vec<(Cost, Path<T>)>, NetErrors>>()?;
+            costed_paths.sort_by_cached_key(|(cost, path)| (*cost, path.hops(), ids(path), labels(path)));
+            paths = costed_paths.into_iter().map(|(_, path)| path).collect();
+        },
+        PathOrder::Lexicographic => paths.sort_by_cached_key(|path| (ids(path), labels(path))),
+    }
+
+    Ok(paths)
+}
+
+fn ids<T: Point>(path: &Path<T>) -> Vec<T::Identifier> {
+    path.points().iter()
+        .map(|point| point.id())
+        .collect()
+}
+
+fn labels<T: Point>(path: &Path<T>) -> Vec<Option<String>> {
+    path.labels().to_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use node::Node;
+    use node::NodeBuilder;
+    use order::*;
+    use path::PathBuilder;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this net of points, with its connections added in reverse order:
+    // A -1- B -1- C
+    //  \    |    /
+    //   1   1   5
+    //    \  |  /
+    //       D
+    fn weighted_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', vec![('D', 1), ('B', 1)]),
+                node('B', vec![('D', 1), ('C', 1), ('A', 1)]),
+                node('C', vec![('D', 5), ('B', 1)]),
+                node('D', vec![('C', 5), ('B', 1), ('A', 1)]),
+            ]
+        }
+    }
+
+    #[test]
+    fn paths_should_keep_the_discovery_order() {
+        assert_eq!(ordered_paths(PathOrder::Discovery), vec!["A-D-C", "A-D-B-C", "A-B-D-C", "A-B-C"]);
+    }
+
+    #[test]
+    fn paths_should_be_ordered_by_hops_and_then_by_ids() {
+        assert_eq!(ordered_paths(PathOrder::Hops), vec!["A-B-C", "A-D-C", "A-B-D-C", "A-D-B-C"]);
+    }
+
+    #[test]
+    fn paths_should_be_ordered_by_cost_and_then_by_hops() {
+        assert_eq!(ordered_paths(PathOrder::Cost), vec!["A-B-C", "A-D-B-C", "A-D-C", "A-B-D-C"]);
+    }
+
+    #[test]
+    fn paths_should_be_ordered_by_ids() {
+        assert_eq!(ordered_paths(PathOrder::Lexicographic), vec!["A-B-C", "A-B-D-C", "A-D-B-C", "A-D-C"]);
+    }
+
+    // Given this net of points, with a train and then a bus from A to B:
+    // A =2/3= B -1- C
+    #[test]
+    fn paths_through_parallel_connections_should_be_ordered_by_the_connection_taken() {
+        let net = Net {
+            nodes: vec![
+                NodeBuilder::new()
+                    .point(&simple_point('A'))
+                    .connected_point_with_label(&simple_point('B'), "train", 2)
+                    .connected_point_with_label(&simple_point('B'), "bus", 3)
+                    .build()
+                    .unwrap(),
+                node('B', vec![('C', 1)]),
+                node('C', vec![]),
+            ]
+        };
+        let labels_in = |order: PathOrder| -> Vec<Option<String>> {
+            net.find_paths_ordered(&simple_point('A'), &simple_point('C'), order)
+                .expect("should find paths from A to C")
+                .iter()
+                .map(|path| path.labels()[0].clone())
+                .collect()
+        };
+        let (train, bus) = (Some(String::from("train")), Some(String::from("bus")));
+
+        assert_eq!(labels_in(PathOrder::Cost), vec![train.clone(), bus.clone()], "the train path costs 3 and the bus path 4");
+        assert_eq!(labels_in(PathOrder::Lexicographic), vec![bus, train]);
+    }
+
+    #[test]
+    fn sorting_by_cost_should_throw_if_a_path_is_not_valid() {
+        let net = weighted_net();
+        let looping_path = PathBuilder::new()
+            .points(vec![simple_point('A'), simple_point('B'), simple_point('A'), simple_point('D')])
+            .build()
+            .unwrap();
+
+        match sort_paths(&NetIndex::new(&net), vec![looping_path], PathOrder::Cost) {
+            Err(NetErrors::DuplicatePoint(point)) => assert_eq!(point, "A"),
+            other => panic!("DuplicatePoint expected, got {:?}", other)
+        }
+    }
+
+    fn ordered_paths(order: PathOrder) -> Vec<String> {
+        weighted_net().find_paths_ordered(&simple_point('A'), &simple_point('C'), order)
+            .expect("should find paths from A to C")
+            .iter()
+            .map(|path| format!("{}", path))
+            .collect()
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connections: Vec<(char, Cost)>) -> Node<SimplePoint> {
+        let mut builder = NodeBuilder::new();
+        builder.point(&simple_point(name));
+        connections.into_iter()
+            .for_each(|(to, cost)| {
+                builder.connected_point_with_cost(&simple_point(to), cost);
+            });
+        builder.build().unwrap()
+    }
+}
@@ -0,0 +1,271 @@
+use compact::CompactNet;
+use index::NetIndex;
+use index::Topology;
+use net;
+use net::Net;
+use net::NetErrors;
+use node::Point;
+use observer::NoObserver;
+use path::Path;
+use std::fmt;
+use std::rc::Rc;
+use std::slice;
+use std::str::FromStr;
+use trail::Trail;
+
+/// Where a paginated path search continues. It is the trail the search was about to follow,
+/// written as the position of its origin in the net followed by the index of the connection
+/// taken at each hop, so it is only valid for the same net.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathCursor {
+    steps: Vec<usize>,
+}
+
+impl PathCursor {
+    fn new(trail: &Trail) -> PathCursor {
+        let mut steps = vec![trail.positions_from_start()[0]];
+        steps.extend(trail.connections_from_start());
+
+        PathCursor { steps }
+    }
+
+    /// Rebuilds the pending trails of the search: the ones branching from every point of the
+    /// cursor trail after it, the trail itself and the origins that were not explored yet.
+    fn pending_trails<T: Point, G: Topology<T>>(&self, graph: &G, origins: &[usize], destinations: &[usize]) -> Result<Vec<Rc<Trail>>, NetErrors> {
+        let invalid_cursor = || NetErrors::InvalidCursor(self.to_string());
+
+        let (&first, rest) = self.steps.split_first().ok_or_else(invalid_cursor)?;
+        let origin_index = origins.iter().position(|&origin| origin == first).ok_or_else(invalid_cursor)?;
+
+        let mut pending_trails: Vec<Rc<Trail>> = origins[origin_index + 1..].iter()
+            .rev()
+            .map(|&origin| Trail::start(origin))
+            .collect();
+        let mut trail = Trail::start(first);
+        for &connection in rest {
+            let connections = graph.connections(trail.last_position());
+            let &(next, _) = connections.get(connection).ok_or_else(invalid_cursor)?;
+            let reaches_destination = trail.hops() > 0 && destinations.contains(&trail.last_position());
+            if (reaches_destination && net::stops_at_destination(&trail, destinations)) || trail.contains(next) {
+                return Err(invalid_cursor());
+            }
+
+            pending_trails.extend(connections.iter()
+                .enumerate()
+                .skip(connection + 1)
+                .rev()
+                .filter(|&(_, &(position, _))| !trail.contains(position))
+                .map(|(sibling, &(position, _))| Trail::extend(&trail, position, sibling)));
+
+            trail = Trail::extend(&trail, next, connection);
+        }
+        pending_trails.push(trail);
+
+        Ok(pending_trails)
+    }
+}
+
+impl fmt::Display for PathCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let steps: Vec<String> = self.steps.iter()
+            .map(|step| step.to_string())
+            .collect();
+        write!(f, "{}", steps.join(","))
+    }
+}
+
+impl FromStr for PathCursor {
+    type Err = NetErrors;
+
+    fn from_str(cursor: &str) -> Result<PathCursor, NetErrors> {
+        cursor.split(',')
+            .map(|step| step.parse::<usize>())
+            .collect::<Result<Vec<usize>, _>>()
+            .map(|steps| PathCursor { steps })
+            .map_err(|_| NetErrors::InvalidCursor(cursor.to_string()))
+    }
+}
+
+/// Paths found by a paginated search, in the same order `find_paths` finds them.
+#[derive(Debug)]
+pub struct PathPage<T: Point> {
+    paths: Vec<Path<T>>,
+    next_cursor: Option<PathCursor>,
+}
+
+impl<T: Point> PathPage<T> {
+    pub fn paths(&self) -> &[Path<T>] {
+        &self.paths
+    }
+
+    pub fn into_paths(self) -> Vec<Path<T>> {
+        self.paths
+    }
+
+    /// Cursor of the following page, if there are trails left to follow. The following
+    /// page may still come back empty when none of them reaches a destination.
+    pub fn next_cursor(&self) -> Option<&PathCursor> {
+        self.next_cursor.as_ref()
+    }
+}
+
+impl<T: Point, P> Net<T, P> {
+    /// Finds up to `page_size` paths, continuing from `cursor` or from the origin without it.
+    /// Fails with `NetErrors::InvalidPageSize` if `page_size` is zero, as no page would ever advance.
+    pub fn find_paths_page(&self, origin: &T, destination: &T, cursor: Option<&PathCursor>, page_size: usize) -> Result<PathPage<T>, NetErrors> {
+        find_paths_page(&NetIndex::new(self), slice::from_ref(origin), slice::from_ref(destination), cursor, page_size)
+    }
+
+    pub fn find_paths_between_sets_page(&self, origins: &[T], destinations: &[T], cursor: Option<&PathCursor>, page_size: usize) -> Result<PathPage<T>, NetErrors> {
+        find_paths_page(&NetIndex::new(self), origins, destinations, cursor, page_size)
+    }
+}
+
+impl<T: Point, P> CompactNet<T, P> {
+    pub fn find_paths_page(&self, origin: &T, destination: &T, cursor: Option<&PathCursor>, page_size: usize) -> Result<PathPage<T>, NetErrors> {
+        find_paths_page(self, slice::from_ref(origin), slice::from_ref(destination), cursor, page_size)
+    }
+
+    pub fn find_paths_between_sets_page(&self, origins: &[T], destinations: &[T], cursor: Option<&PathCursor>, page_size: usize) -> Result<PathPage<T>, NetErrors> {
+        find_paths_page(self, origins, destinations, cursor, page_size)
+    }
+}
+
+fn find_paths_page<T, G>(graph: &G, origins: &[T], destinations: &[T], cursor: Option<&PathCursor>, page_size: usize) -> Result<PathPage<T>, NetErrors>
+    where T: Point, G: Topology<T> {
+    if page_size == 0 {
+        return Err(NetErrors::InvalidPageSize);
+    }
+
+    let origins = graph.positions(origins)?;
+    let destinations = graph.distinct_positions(destinations)?;
+
+    let mut pending_trails = match cursor {
+        Some(cursor) => cursor.pending_trails(graph, &origins, &destinations)?,
+        None => origins.iter().rev().map(|&origin| Trail::start(origin)).collect()
+    };
+    let mut paths = Vec::new();
+    net::follow_trails_to_destinations(graph, &destinations, &mut pending_trails, &mut paths, page_size, &|_, _| true, &mut NoObserver);
+
+    let next_cursor = pending_trails.last()
+        .map(|trail| PathCursor::new(trail));
+
+    if cursor.is_none() && paths.is_empty() && next_cursor.is_none() {
+        Err(NetErrors::NoPathFound)
+    } else {
+        Ok(PathPage { paths, next_cursor })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use node::Node;
+    use node::NodeBuilder;
+    use page::*;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this net of points:
+    // A - B - C
+    //  \  |  /
+    //   \ | /
+    //     D
+    fn diamond_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', "BD"),
+                node('B', "ACD"),
+                node('C', "BD"),
+                node('D', "ACB"),
+            ]
+        }
+    }
+
+    #[test]
+    fn pages_should_return_the_paths_of_find_paths_in_the_same_order() {
+        let net = diamond_net();
+        let (a, c) = (simple_point('A'), simple_point('C'));
+
+        let mut paged_paths = Vec::new();
+        let mut cursor: Option<PathCursor> = None;
+        loop {
+            let page = net.find_paths_page(&a, &c, cursor.as_ref(), 3).expect("should find a page of paths");
+            // The cursor goes through a string, as it would between two requests
+            cursor = page.next_cursor().map(|cursor| cursor.to_string().parse().unwrap());
+            paged_paths.extend(format_paths(page.paths()));
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        assert_eq!(paged_paths, format_paths(&net.find_paths(&a, &c).unwrap()));
+    }
+
+    #[test]
+    fn pages_should_continue_through_the_following_origins() {
+        let net = diamond_net();
+        let (origins, destinations) = (points("AB"), points("CD"));
+
+        let first_page = net.find_paths_between_sets_page(&origins, &destinations, None, 5).unwrap();
+        let second_page = net.find_paths_between_sets_page(&origins, &destinations, first_page.next_cursor(), 100).unwrap();
+
+        let mut paged_paths = format_paths(first_page.paths());
+        paged_paths.extend(format_paths(second_page.paths()));
+        assert_eq!(paged_paths, format_paths(&net.find_paths_between_sets(&origins, &destinations).unwrap()));
+        assert_eq!(second_page.next_cursor(), None);
+    }
+
+    #[test]
+    fn cursor_not_continuing_a_search_should_throw() {
+        let net = diamond_net();
+        let cursor: PathCursor = "0,2".parse().expect("should parse the steps");
+
+        match net.find_paths_page(&simple_point('A'), &simple_point('C'), Some(&cursor), 1) {
+            Err(NetErrors::InvalidCursor(cursor)) => assert_eq!(cursor, "0,2"),
+            other => panic!("InvalidCursor expected, got {:?}", other)
+        }
+        assert!("0,x".parse::<PathCursor>().is_err());
+    }
+
+    #[test]
+    fn empty_page_size_should_throw() {
+        let net = diamond_net();
+
+        match net.find_paths_page(&simple_point('A'), &simple_point('C'), None, 0) {
+            Err(NetErrors::InvalidPageSize) => {},
+            other => panic!("InvalidPageSize expected, got {:?}", other)
+        }
+    }
+
+    fn format_paths(paths: &[Path<SimplePoint>]) -> Vec<String> {
+        paths.iter().map(|path| format!("{}", path)).collect()
+    }
+
+    fn points(names: &str) -> Vec<SimplePoint> {
+        names.chars().map(simple_point).collect()
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connected_to: &str) -> Node<SimplePoint> {
+        let connected_points: Vec<SimplePoint> = connected_to.chars().map(simple_point).collect();
+        NodeBuilder::new()
+            .point(&simple_point(name))
+            .connected_points(&connected_points)
+            .build()
+            .unwrap()
+    }
+}
@@ -0,0 +1,189 @@
+use compact::CompactNet;
+use constrained;
+use constrained::Label;
+use index::NetIndex;
+use index::Topology;
+use net::Net;
+use net::NetErrors;
+use node::Cost;
+use node::Point;
+use path::Path;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Path that no other path beats in every criterion, with how much it costs in each one.
+#[derive(Debug)]
+pub struct ParetoPath<T: Point> {
+    path: Path<T>,
+    costs: Vec<Cost>,
+}
+
+impl<T: Point> ParetoPath<T> {
+    pub fn path(&self) -> &Path<T> {
+        &self.path
+    }
+
+    pub fn costs(&self) -> &[Cost] {
+        &self.costs
+    }
+}
+
+impl<T: Point, P> Net<T, P> {
+    /// Finds every path from the origin to the destination not dominated by another one, taking
+    /// the resources of the connections as criteria. Paths costing the same keep only one of them.
+    pub fn pareto_paths(&self, origin: &T, destination: &T) -> Result<Vec<ParetoPath<T>>, NetErrors> {
+        pareto_paths(&NetIndex::new(self), origin, destination)
+    }
+}
+
+impl<T: Point, P> CompactNet<T, P> {
+    pub fn pareto_paths(&self, origin: &T, destination: &T) -> Result<Vec<ParetoPath<T>>, NetErrors> {
+        pareto_paths(self, origin, destination)
+    }
+}
+
+fn pareto_paths<T: Point, G: Topology<T>>(graph: &G, origin: &T, destination: &T) -> Result<Vec<ParetoPath<T>>, NetErrors> {
+    let origin = graph.position(origin)?;
+    let destination = graph.position(destination)?;
+
+    let criteria = (0..graph.len())
+        .flat_map(|position| (0..graph.connections(position).len()).map(move |connection| graph.resources(position, connection).len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut labels = vec![Label { position: origin, resources: vec![0; criteria], previous: None }];
+    let mut kept_labels: Vec<Vec<usize>> = vec![Vec::new(); graph.len()];
+    let mut pending = BinaryHeap::new();
+    pending.push(Reverse((labels[0].resources.clone(), 0)));
+
+    // Labels come out in lexicographic order, so none of them is dominated by a later one
+    while let Some(Reverse((_, label_index))) = pending.pop() {
+        let label = &labels[label_index];
+        let is_dominated = |position: usize| kept_labels[position].iter()
+            .any(|&kept| labels[kept].dominates(label));
+        if is_dominated(label.position) || is_dominated(destination) {
+            continue;
+        }
+        kept_labels[label.position].push(label_index);
+        if label.position == destination {
+            continue;
+        }
+
+        let next_labels: Vec<Label> = graph.connections(label.position).iter()
+            .enumerate()
+            .map(|(connection, &(next, _))| Label {
+                position: next,
+                resources: constrained::add_resources(&label.resources, graph.resources(label.position, connection)),
+                previous: Some((label_index, connection)),
+            })
+            .collect();
+        for next_label in next_labels {
+            pending.push(Reverse((next_label.resources.clone(), labels.len())));
+            labels.push(next_label);
+        }
+    }
+
+    let pareto_paths: Vec<ParetoPath<T>> = kept_labels[destination].iter()
+        .map(|&label_index| ParetoPath {
+            path: constrained::labels_path(graph, &labels, label_index),
+            costs: labels[label_index].resources.clone(),
+        })
+        .collect();
+
+    if pareto_paths.is_empty() {
+        Err(NetErrors::NoPathFound)
+    } else {
+        Ok(pareto_paths)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use node::Node;
+    use node::NodeBuilder;
+    use pareto::*;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this net of points, with the (time, money) of each connection:
+    //      (1,5) B (1,5)
+    //     /             \
+    // A --(3,3)-- C --(3,3)-- E
+    //     \             /
+    //      (2,6) D (2,6)
+    fn multi_criteria_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', vec![('B', vec![1, 5]), ('C', vec![3, 3]), ('D', vec![2, 6])]),
+                node('B', vec![('E', vec![1, 5])]),
+                node('C', vec![('E', vec![3, 3])]),
+                node('D', vec![('E', vec![2, 6])]),
+                node('E', vec![]),
+            ]
+        }
+    }
+
+    #[test]
+    fn pareto_paths_should_keep_only_the_non_dominated_paths() {
+        let net = multi_criteria_net();
+
+        let pareto_paths: Vec<(String, Vec<Cost>)> = net.pareto_paths(&simple_point('A'), &simple_point('E'))
+            .expect("should find paths from A to E")
+            .iter()
+            .map(|pareto_path| (format!("{}", pareto_path.path()), pareto_path.costs().to_vec()))
+            .collect();
+
+        assert_eq!(pareto_paths, vec![
+            (String::from("A-B-E"), vec![2, 10]),
+            (String::from("A-C-E"), vec![6, 6]),
+        ], "A-D-E is slower and more expensive than A-B-E");
+    }
+
+    #[test]
+    fn pareto_paths_should_throw_if_there_is_no_path() {
+        let net = multi_criteria_net();
+
+        match net.pareto_paths(&simple_point('E'), &simple_point('A')) {
+            Err(NetErrors::NoPathFound) => {},
+            other => panic!("NoPathFound expected, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn pareto_paths_should_find_the_same_paths_on_a_compact_net() {
+        let net = multi_criteria_net();
+        let costs = |pareto_paths: Vec<ParetoPath<SimplePoint>>| -> Vec<Vec<Cost>> {
+            pareto_paths.iter().map(|pareto_path| pareto_path.costs().to_vec()).collect()
+        };
+
+        let pareto_paths = net.pareto_paths(&simple_point('A'), &simple_point('E')).expect("should find paths from A to E");
+        let compact_pareto_paths = net.compact().pareto_paths(&simple_point('A'), &simple_point('E')).expect("should find paths from A to E");
+
+        assert_eq!(costs(compact_pareto_paths), costs(pareto_paths));
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connections: Vec<(char, Vec<Cost>)>) -> Node<SimplePoint> {
+        let mut builder = NodeBuilder::new();
+        builder.point(&simple_point(name));
+        connections.into_iter()
+            .for_each(|(to, costs)| {
+                builder.connected_point_with_resources(&simple_point(to), costs[0], &costs);
+            });
+        builder.build().unwrap()
+    }
+}
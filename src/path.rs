@@ -3,14 +3,31 @@ use std::fmt;
 
 #[derive(Debug, Clone)]
 pub struct Path<T: Point> {
-    points: Vec<T>
+    points: Vec<T>,
+    labels: Vec<Option<String>>,
 }
 
 impl<T: Point> Path<T> {
     pub fn push(&mut self, point: T) {
+        if !self.points.is_empty() {
+            self.labels.push(None);
+        }
         self.points.push(point);
     }
 
+    pub fn points(&self) -> &[T] {
+        &self.points
+    }
+
+    /// Label of the connection taken at each hop, if it has one.
+    pub fn labels(&self) -> &[Option<String>] {
+        &self.labels
+    }
+
+    pub fn hops(&self) -> usize {
+        self.points.len().saturating_sub(1)
+    }
+
     pub fn do_not_contains(&self, point_to_check: &T) -> bool {
         !self.points.iter().any(|point_in_path| point_in_path.is(point_to_check))
     }
@@ -41,13 +58,21 @@ impl<T: Point> fmt::Display for Path<T> {
 }
 
 pub struct PathBuilder<T: Point> {
-    points: Option<Vec<T>>
+    points: Option<Vec<T>>,
+    labels: Option<Vec<Option<String>>>,
+}
+
+impl<T: Point> Default for PathBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T: Point> PathBuilder<T> {
     pub fn new() -> PathBuilder<T> {
         let points = None;
-        PathBuilder { points }
+        let labels = None;
+        PathBuilder { points, labels }
     }
 
     pub fn points(&mut self, points: Vec<T>) -> &mut Self {
@@ -67,13 +92,24 @@ impl<T: Point> PathBuilder<T> {
         self
     }
 
+    pub fn labels(&mut self, labels: Vec<Option<String>>) -> &mut Self {
+        self.labels = Some(labels);
+        self
+    }
+
     pub fn build(&self) -> Result<Path<T>, String> {
         let points = Clone::clone(
             self.points
                 .as_ref()
                 .ok_or("Should set at least one point for the path")?
         );
-        Ok(Path { points })
+        let hops = points.len().saturating_sub(1);
+        let labels = match self.labels {
+            Some(ref labels) if labels.len() != hops => return Err(String::from("Should set a label for each hop")),
+            Some(ref labels) => labels.clone(),
+            None => vec![None; hops]
+        };
+        Ok(Path { points, labels })
     }
 }
 
@@ -129,6 +165,16 @@ mod test {
         assert!(builder.build().is_err(), "Should throw an error if no point is provided");
     }
 
+    #[test]
+    fn builder_should_throw_if_labels_do_not_match_the_hops() {
+        let path = PathBuilder::new()
+            .points(vec![SimplePoint::new(8), SimplePoint::new(5)])
+            .labels(vec![Some(String::from("bus")), None])
+            .build();
+
+        assert_eq!(path.err(), Some(String::from("Should set a label for each hop")));
+    }
+
     fn format_path_with_dashes_between_ids(path: Path<SimplePoint>) -> String {
         let ids_as_string: Vec<String> = path.points
             .iter()
@@ -0,0 +1,182 @@
+use compact::CompactNet;
+use index::NetIndex;
+use index::Reweighted;
+use index::Topology;
+use net;
+use net::Net;
+use net::NetErrors;
+use node::Cost;
+use node::Point;
+use observer::NoObserver;
+use path::Path;
+use shortest_path;
+use std::slice;
+
+impl<T: Point, P> Net<T, P> {
+    /// Finds all the paths following only the connections `follows` accepts,
+    /// given the points of each connection and its payload.
+    pub fn find_paths_following<F>(&self, origin: &T, destination: &T, follows: F) -> Result<Vec<Path<T>>, NetErrors>
+        where F: Fn(&T, &T, &P) -> bool {
+        find_paths_following(&NetIndex::new(self), origin, destination, follows)
+    }
+
+    /// Finds the cheapest path taking the cost of each connection from `cost`, given its points,
+    /// the cost it was added with and its payload.
+    pub fn shortest_path_with_costs<F>(&self, origin: &T, destination: &T, cost: F) -> Result<Path<T>, NetErrors>
+        where F: Fn(&T, &T, Cost, &P) -> Cost {
+        shortest_path_with_costs(&NetIndex::new(self), origin, destination, cost)
+    }
+}
+
+impl<T: Point, P> CompactNet<T, P> {
+    pub fn find_paths_following<F>(&self, origin: &T, destination: &T, follows: F) -> Result<Vec<Path<T>>, NetErrors>
+        where F: Fn(&T, &T, &P) -> bool {
+        find_paths_following(self, origin, destination, follows)
+    }
+
+    pub fn shortest_path_with_costs<F>(&self, origin: &T, destination: &T, cost: F) -> Result<Path<T>, NetErrors>
+        where F: Fn(&T, &T, Cost, &P) -> Cost {
+        shortest_path_with_costs(self, origin, destination, cost)
+    }
+}
+
+fn find_paths_following<T, G, F>(graph: &G, origin: &T, destination: &T, follows: F) -> Result<Vec<Path<T>>, NetErrors>
+    where T: Point, G: Topology<T>, F: Fn(&T, &T, &G::Payload) -> bool {
+    let graph = Reweighted::new(graph, |from, to, cost, payload| if follows(from, to, payload) { Some(cost) } else { None });
+    net::find_paths_between_sets(&graph, slice::from_ref(origin), slice::from_ref(destination), &mut NoObserver)
+}
+
+fn shortest_path_with_costs<T, G, F>(graph: &G, origin: &T, destination: &T, cost: F) -> Result<Path<T>, NetErrors>
+    where T: Point, G: Topology<T>, F: Fn(&T, &T, Cost, &G::Payload) -> Cost {
+    let graph = Reweighted::new(graph, |from, to, connection_cost, payload| Some(cost(from, to, connection_cost, payload)));
+    shortest_path::shortest_path_between_sets(&graph, slice::from_ref(origin), slice::from_ref(destination), &mut NoObserver)
+}
+
+#[cfg(test)]
+mod test {
+    use node::ConnectionBuilder;
+    use node::Node;
+    use node::NodeBuilder;
+    use payload::*;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Road {
+        toll: Cost,
+        closed: bool,
+    }
+
+    // Given this net of roads, where A-B-C is shorter but has a toll at B-C:
+    // A -1- B -1- C
+    //  \         /
+    //   3       3
+    //    \     /
+    //       D
+    fn roads_net() -> Net<SimplePoint, Road> {
+        Net {
+            nodes: vec![
+                node('A', vec![('B', 1, Road::default()), ('D', 3, Road::default())]),
+                node('B', vec![('C', 1, Road { toll: 10, closed: false })]),
+                node('C', vec![]),
+                node('D', vec![('C', 3, Road::default())]),
+            ]
+        }
+    }
+
+    #[test]
+    fn payload_should_be_kept_by_the_connection() {
+        let net = roads_net();
+
+        assert_eq!(net.payload_between(&simple_point('B'), &simple_point('C')), Some(&Road { toll: 10, closed: false }));
+        assert_eq!(net.payload_between(&simple_point('C'), &simple_point('B')), None);
+    }
+
+    #[test]
+    fn shortest_path_should_use_the_costs_computed_from_payloads() {
+        let net = roads_net();
+
+        let path = net.shortest_path_with_costs(&simple_point('A'), &simple_point('C'), |_, _, cost, road| cost + road.toll)
+            .expect("should find a path from A to C");
+
+        assert_eq!(format!("{}", path), "A-D-C", "the toll makes A-B-C cost 12");
+    }
+
+    #[test]
+    fn find_paths_should_only_follow_the_accepted_connections() {
+        let mut net = roads_net();
+        net.nodes[3] = node('D', vec![('C', 3, Road { toll: 0, closed: true })]);
+
+        let paths: Vec<String> = net.find_paths_following(&simple_point('A'), &simple_point('C'), |_, _, road| !road.closed)
+            .expect("should find paths from A to C")
+            .iter()
+            .map(|path| format!("{}", path))
+            .collect();
+
+        assert_eq!(paths, vec!["A-B-C"]);
+    }
+
+    #[test]
+    fn compact_net_should_use_the_payloads_of_its_connections() {
+        let mut net = roads_net();
+        net.nodes[3] = node('D', vec![('C', 3, Road { toll: 0, closed: true })]);
+        let compact_net = net.compact();
+
+        let path = compact_net.shortest_path_with_costs(&simple_point('A'), &simple_point('C'), |_, _, cost, road| cost + road.toll)
+            .expect("should find a path from A to C");
+        let paths = compact_net.find_paths_following(&simple_point('A'), &simple_point('C'), |_, _, road| !road.closed)
+            .expect("should find paths from A to C");
+
+        assert_eq!(format!("{}", path), "A-D-C");
+        assert_eq!(paths.len(), 1);
+        assert_eq!(format!("{}", paths[0]), "A-B-C");
+    }
+
+    #[test]
+    fn searches_should_use_the_payload_of_each_labeled_connection() {
+        let (a, b) = (simple_point('A'), simple_point('B'));
+        let net = Net {
+            nodes: vec![
+                NodeBuilder::default()
+                    .point(&a)
+                    .connected_point_through(&b, ConnectionBuilder::with_payload(1, Road { toll: 10, closed: false }).label("motorway"))
+                    .connected_point_through(&b, ConnectionBuilder::with_payload(3, Road::default()).label("old road"))
+                    .build()
+                    .unwrap(),
+                node('B', vec![]),
+            ]
+        };
+
+        let quickest = net.shortest_path(&a, &b).expect("should find a path from A to B");
+        let cheapest = net.shortest_path_with_costs(&a, &b, |_, _, cost, road| cost + road.toll)
+            .expect("should find a path from A to B");
+
+        assert_eq!(quickest.labels(), &[Some(String::from("motorway"))]);
+        assert_eq!(cheapest.labels(), &[Some(String::from("old road"))], "the toll makes the motorway cost 11");
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connections: Vec<(char, Cost, Road)>) -> Node<SimplePoint, Road> {
+        let mut builder = NodeBuilder::default();
+        builder.point(&simple_point(name));
+        connections.into_iter()
+            .for_each(|(to, cost, road)| {
+                builder.connected_point_with_payload(&simple_point(to), cost, road);
+            });
+        builder.build().unwrap()
+    }
+}
@@ -0,0 +1,404 @@
+use index::NetIndex;
+use index::Topology;
+use net::Net;
+use net::NetErrors;
+use observer::NoObserver;
+use observer::SearchControl;
+use observer::SearchObserver;
+use node::Cost;
+use node::Point;
+use path::Path;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::slice;
+
+impl<T: Point, P> Net<T, P> {
+    /// Finds the cheapest path. Of the paths that cost the same, it takes the one with the
+    /// fewest hops and then the one going first through the points added first to the net.
+    pub fn shortest_path(&self, origin: &T, destination: &T) -> Result<Path<T>, NetErrors> {
+        self.shortest_path_observed(origin, destination, &mut NoObserver)
+    }
+
+    pub fn shortest_path_observed<O>(&self, origin: &T, destination: &T, observer: &mut O) -> Result<Path<T>, NetErrors>
+        where O: SearchObserver<T, P> + ?Sized {
+        shortest_path_between_sets(&NetIndex::new(self), slice::from_ref(origin), slice::from_ref(destination), observer)
+    }
+
+    /// Finds the cheapest path starting at any of the origins and ending at any of the destinations.
+    /// A point that is both an origin and a destination is never a path on its own.
+    pub fn shortest_path_between_sets(&self, origins: &[T], destinations: &[T]) -> Result<Path<T>, NetErrors> {
+        shortest_path_between_sets(&NetIndex::new(self), origins, destinations, &mut NoObserver)
+    }
+
+    /// Finds the cheapest path for every origin and destination pair that are connected,
+    /// exploring the net once per origin. Pairs of the same point are left out.
+    pub fn shortest_paths_per_pair(&self, origins: &[T], destinations: &[T]) -> Result<Vec<Path<T>>, NetErrors> {
+        shortest_paths_per_pair(&NetIndex::new(self), origins, destinations, &mut NoObserver)
+    }
+}
+
+/// Paths never go from a point to itself, as `find_paths_between_sets` does: a point that is
+/// both an origin and a destination is only the destination of the paths from other origins.
+/// The origins that are not destinations are searched from all at once, and each other one alone.
+pub(crate) fn shortest_path_between_sets<T, G, O>(graph: &G, origins: &[T], destinations: &[T], observer: &mut O) -> Result<Path<T>, NetErrors>
+    where T: Point, G: Topology<T>, O: SearchObserver<T, G::Payload> + ?Sized {
+    let origins = graph.positions(origins)?;
+    let destinations = graph.positions(destinations)?;
+
+    let (destination_origins, other_origins): (Vec<usize>, Vec<usize>) = origins.iter()
+        .partition(|origin| destinations.contains(origin));
+    let searches = Some(other_origins).into_iter()
+        .filter(|sources| !sources.is_empty())
+        .chain(destination_origins.into_iter().map(|origin| vec![origin]));
+
+    let mut cheapest: Option<(Cost, usize, ShortestPathTree)> = None;
+    for sources in searches {
+        let tree = dijkstra(graph, &sources, |position| destinations.contains(&position) && !sources.contains(&position), observer);
+        if tree.stopped {
+            return Err(NetErrors::Stopped);
+        }
+        if let Some(reached) = tree.reached {
+            let cost = tree.costs[reached].expect("reached points have a cost");
+            if cheapest.as_ref().is_none_or(|&(cheapest_cost, _, _)| cost < cheapest_cost) {
+                cheapest = Some((cost, reached, tree));
+            }
+        }
+    }
+
+    match cheapest {
+        Some((_, destination, tree)) => {
+            let path = tree.path_to(graph, destination);
+            observer.on_path_found(&path);
+            Ok(path)
+        },
+        None => Err(NetErrors::NoPathFound)
+    }
+}
+
+/// Stopping the search keeps the paths found until then, failing with `NetErrors::Stopped`
+/// only if there are none, as `find_paths_between_sets` does.
+pub(crate) fn shortest_paths_per_pair<T, G, O>(graph: &G, origins: &[T], destinations: &[T], observer: &mut O) -> Result<Vec<Path<T>>, NetErrors>
+    where T: Point, G: Topology<T>, O: SearchObserver<T, G::Payload> + ?Sized {
+    let origins = graph.positions(origins)?;
+    let destinations = graph.positions(destinations)?;
+
+    let mut paths: Vec<Path<T>> = Vec::new();
+    let mut stopped = false;
+    'origins: for origin in origins {
+        let tree = dijkstra(graph, &[origin], |_| false, observer);
+        if tree.stopped {
+            stopped = true;
+            break;
+        }
+
+        for destination in &destinations {
+            if *destination != origin && tree.costs[*destination].is_some() {
+                let path = tree.path_to(graph, *destination);
+                let control = observer.on_path_found(&path);
+                paths.push(path);
+                if control == SearchControl::Stop {
+                    break 'origins;
+                }
+            }
+        }
+    }
+
+    if paths.is_empty() && stopped {
+        Err(NetErrors::Stopped)
+    } else if paths.is_empty() {
+        Err(NetErrors::NoPathFound)
+    } else {
+        Ok(paths)
+    }
+}
+
+pub(crate) struct ShortestPathTree {
+    pub costs: Vec<Option<Cost>>,
+    pub hops: Vec<usize>,
+    pub previous: Vec<Option<usize>>,
+    /// Index of the connection taken from the previous point, among the connections of that point.
+    pub previous_connection: Vec<usize>,
+    pub origins: Vec<usize>,
+    pub settled: Vec<usize>,
+    pub reached: Option<usize>,
+    pub stopped: bool,
+}
+
+impl ShortestPathTree {
+    /// Path from the origins to a settled point, breaking ties as `cheapest_path` does.
+    pub fn path_to<T: Point, G: Topology<T>>(&self, graph: &G, destination: usize) -> Path<T> {
+        let mut from_origins = vec![None; graph.len()];
+        for &position in &self.settled {
+            from_origins[position] = self.costs[position].map(|cost| (cost, self.hops[position]));
+        }
+        let mut to_destination = vec![None; graph.len()];
+        to_destination[destination] = Some((0, 0));
+        let best = from_origins[destination].expect("the destination should be settled");
+
+        cheapest_path(graph, &self.origins, destination, &from_origins, &self.settled, &to_destination, best)
+    }
+}
+
+/// Cost and then hops of a path, which is how searches tell paths apart.
+pub(crate) type Distance = (Cost, usize);
+
+/// Among the cheapest paths from any of the origins to the destination, the one with the fewest
+/// hops that goes first through the points added first to the net, so searches settling points in
+/// different orders return the same path. `from_origins` has the distances from the origins of the
+/// points settled in the order of `settled`, and `to_destination` the distances to the destination
+/// of the points settled searching backwards. Every point of the cheapest paths must be in one of them.
+pub(crate) fn cheapest_path<T, G>(graph: &G, origins: &[usize], destination: usize, from_origins: &[Option<Distance>],
+                                  settled: &[usize], to_destination: &[Option<Distance>], best: Distance) -> Path<T>
+    where T: Point, G: Topology<T> {
+    let add = |(cost, hops): Distance, (other_cost, other_hops): Distance| (cost + other_cost, hops + other_hops);
+    // Whether a connection leaving a point of the cheapest paths, at the given distance
+    // from the origins, is followed by them, given the points known to be on them.
+    let is_followed = |on_path: &[bool], from: Distance, next: usize, cost: Cost| {
+        let through = add(from, (cost, 1));
+        match to_destination[next] {
+            Some(to) => add(through, to) == best,
+            None => from_origins[next] == Some(through) && on_path[next]
+        }
+    };
+
+    // Settled points only lead to points settled after them, so going backwards
+    // each of them is on a cheapest path if it is followed by one.
+    let mut on_path = vec![false; graph.len()];
+    for &position in settled.iter().rev() {
+        on_path[position] = match (from_origins[position], to_destination[position]) {
+            (Some(from), Some(to)) => add(from, to) == best,
+            (Some(from), None) => graph.connections(position).iter()
+                .any(|&(next, cost)| is_followed(&on_path, from, next, cost)),
+            (None, _) => false
+        };
+    }
+
+    let mut current = *origins.iter()
+        .filter(|&&origin| match to_destination[origin] {
+            Some(to) => to == best,
+            None => on_path[origin]
+        })
+        .min()
+        .expect("an origin should start the cheapest paths");
+    let mut positions = vec![current];
+    let mut connections = Vec::new();
+    while current != destination {
+        let from = from_origins[current]
+            .or_else(|| to_destination[current].map(|(cost, hops)| (best.0 - cost, best.1 - hops)))
+            .expect("points of the cheapest paths should be settled");
+        let (next, connection) = graph.connections(current).iter().enumerate()
+            .filter(|&(_, &(next, cost))| is_followed(&on_path, from, next, cost))
+            .map(|(connection, &(next, _))| (next, connection))
+            .min()
+            .expect("the cheapest paths should go on until the destination");
+        positions.push(next);
+        connections.push(connection);
+        current = next;
+    }
+
+    graph.path_through(&positions, &connections)
+}
+
+/// Settles points in cost and then hops order starting from all the origins at once, until `stop`
+/// accepts a settled point, the observer stops the search or every reachable point is settled.
+pub(crate) fn dijkstra<T, G, F, O>(graph: &G, origins: &[usize], mut stop: F, observer: &mut O) -> ShortestPathTree
+    where T: Point, G: Topology<T>, F: FnMut(usize) -> bool, O: SearchObserver<T, G::Payload> + ?Sized {
+    let mut costs: Vec<Option<Cost>> = vec![None; graph.len()];
+    let mut previous: Vec<Option<usize>> = vec![None; graph.len()];
+    let mut previous_connection = vec![0; graph.len()];
+    let mut hops = vec![0; graph.len()];
+    let mut is_settled = vec![false; graph.len()];
+    let mut settled = Vec::new();
+    let mut pending = BinaryHeap::new();
+
+    for origin in origins {
+        costs[*origin] = Some(0);
+        pending.push(Reverse(((0, 0), *origin)));
+    }
+    let tree = |costs, hops, previous, previous_connection, settled, reached, stopped| ShortestPathTree {
+        costs, hops, previous, previous_connection, origins: origins.to_vec(), settled, reached, stopped
+    };
+
+    while let Some(Reverse(((cost, position_hops), position))) = pending.pop() {
+        if is_settled[position] {
+            continue;
+        }
+        is_settled[position] = true;
+        settled.push(position);
+
+        if stop(position) {
+            return tree(costs, hops, previous, previous_connection, settled, Some(position), false);
+        }
+
+        let arrived_through = previous[position]
+            .map(|previous_position| graph.payload(previous_position, previous_connection[position]));
+        if observer.on_expand(graph.point_at(position), position_hops, arrived_through) == SearchControl::Stop {
+            return tree(costs, hops, previous, previous_connection, settled, None, true);
+        }
+
+        for (connection, &(next, connection_cost)) in graph.connections(position).iter().enumerate() {
+            if is_settled[next] {
+                if observer.on_prune(graph.point_at(position), graph.point_at(next), graph.payload(position, connection)) == SearchControl::Stop {
+                    return tree(costs, hops, previous, previous_connection, settled, None, true);
+                }
+                continue;
+            }
+
+            let next_distance = (cost + connection_cost, position_hops + 1);
+            if costs[next].is_none_or(|known_cost| next_distance < (known_cost, hops[next])) {
+                costs[next] = Some(next_distance.0);
+                previous[next] = Some(position);
+                previous_connection[next] = connection;
+                hops[next] = next_distance.1;
+                pending.push(Reverse((next_distance, next)));
+            }
+        }
+    }
+
+    tree(costs, hops, previous, previous_connection, settled, None, false)
+}
+
+#[cfg(test)]
+mod test {
+    use net::*;
+    use shortest_path::*;
+    use node::Node;
+    use node::NodeBuilder;
+    use node::Cost;
+    use node::Point;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this net of points:
+    // A -1- B -1- C
+    //  \         /
+    //   5       1
+    //    \     /
+    //       D -4- E
+    fn weighted_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', vec![('B', 1), ('D', 5)]),
+                node('B', vec![('A', 1), ('C', 1)]),
+                node('C', vec![('B', 1), ('D', 1)]),
+                node('D', vec![('A', 5), ('C', 1), ('E', 4)]),
+                node('E', vec![('D', 4)]),
+            ]
+        }
+    }
+
+    #[test]
+    fn shortest_path_should_prefer_the_cheapest_path_over_the_shortest_one() {
+        let net = weighted_net();
+
+        let path = net.shortest_path(&simple_point('A'), &simple_point('D'))
+            .expect("should find a path from A to D");
+
+        assert_eq!(format!("{}", path), "A-B-C-D");
+    }
+
+    #[test]
+    fn shortest_path_between_sets_should_return_the_overall_cheapest_path() {
+        let net = weighted_net();
+
+        let path = net.shortest_path_between_sets(&points("AE"), &points("CB"))
+            .expect("should find a path from A or E to B or C");
+
+        assert_eq!(format!("{}", path), "A-B");
+    }
+
+    #[test]
+    fn shortest_paths_per_pair_should_return_a_path_for_each_pair() {
+        let net = weighted_net();
+
+        let paths: Vec<String> = net.shortest_paths_per_pair(&points("AE"), &points("CD"))
+            .expect("should find paths from A and E to C and D")
+            .iter()
+            .map(|path| format!("{}", path))
+            .collect();
+
+        assert_eq!(paths, vec!["A-B-C", "A-B-C-D", "E-D-C", "E-D"]);
+    }
+
+    #[test]
+    fn set_searches_should_not_return_a_path_from_a_point_to_itself() {
+        let net = weighted_net();
+
+        let path = net.shortest_path_between_sets(&points("BE"), &points("BD"))
+            .expect("should find a path from B or E to B or D");
+        let paths: Vec<String> = net.shortest_paths_per_pair(&points("AB"), &points("AB"))
+            .expect("should find paths between A and B")
+            .iter()
+            .map(|path| format!("{}", path))
+            .collect();
+
+        assert_eq!(format!("{}", path), "B-C-D", "B is a destination only for the paths from E");
+        assert_eq!(paths, vec!["A-B", "B-A"]);
+        match net.shortest_path(&simple_point('A'), &simple_point('A')) {
+            Err(NetErrors::NoPathFound) => {},
+            other => panic!("NoPathFound expected, got {:?}", other)
+        }
+    }
+
+    struct StopOnExpand;
+
+    impl SearchObserver<SimplePoint> for StopOnExpand {
+        fn on_expand(&mut self, _point: &SimplePoint, _depth: usize, _arrived_through: Option<&()>) -> SearchControl {
+            SearchControl::Stop
+        }
+    }
+
+    #[test]
+    fn set_searches_stopped_before_finding_a_path_should_fail_the_same_way() {
+        let net = weighted_net();
+        let index = NetIndex::new(&net);
+
+        let cheapest = shortest_path_between_sets(&index, &points("A"), &points("D"), &mut StopOnExpand);
+        let per_pair = shortest_paths_per_pair(&index, &points("A"), &points("D"), &mut StopOnExpand);
+
+        match (cheapest, per_pair) {
+            (Err(NetErrors::Stopped), Err(NetErrors::Stopped)) => {},
+            other => panic!("Stopped expected from both, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn shortest_path_should_throw_if_there_is_no_path() {
+        let mut net = weighted_net();
+        net.nodes.push(node('F', vec![]));
+
+        match net.shortest_path(&simple_point('A'), &simple_point('F')) {
+            Err(NetErrors::NoPathFound) => {},
+            other => panic!("NoPathFound expected, got {:?}", other)
+        }
+    }
+
+    fn points(names: &str) -> Vec<SimplePoint> {
+        names.chars().map(simple_point).collect()
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connections: Vec<(char, Cost)>) -> Node<SimplePoint> {
+        let mut builder = NodeBuilder::new();
+        builder.point(&simple_point(name));
+        connections.into_iter()
+            .for_each(|(to, cost)| {
+                builder.connected_point_with_cost(&simple_point(to), cost);
+            });
+        builder.build().unwrap()
+    }
+}
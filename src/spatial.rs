@@ -0,0 +1,248 @@
+use geo::GeoPoint;
+use geo::EARTH_RADIUS;
+use net::Net;
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+
+type Cartesian = [f64; 3];
+
+/// Point of the index with its position on the unit sphere.
+type Located<T> = (T, Cartesian);
+
+/// K-d tree over the points of a net, to find the points closest to any coordinates.
+/// Points are placed on a unit sphere, where the straight distance between two points
+/// grows with their great circle distance.
+pub struct SpatialIndex<'a, T: GeoPoint + 'a> {
+    points: Vec<Located<&'a T>>,
+}
+
+impl<T: GeoPoint, P> Net<T, P> {
+    pub fn spatial_index(&self) -> SpatialIndex<'_, T> {
+        SpatialIndex::new(self.iter_points())
+    }
+}
+
+impl<'a, T: GeoPoint + 'a> SpatialIndex<'a, T> {
+    pub fn new<I: IntoIterator<Item=&'a T>>(points: I) -> SpatialIndex<'a, T> {
+        let mut points: Vec<Located<&'a T>> = points.into_iter()
+            .map(|point| (point, cartesian(point.latitude(), point.longitude())))
+            .collect();
+        arrange(&mut points, 0);
+
+        SpatialIndex { points }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Point closest to the coordinates, if the index has any.
+    pub fn nearest(&self, latitude: f64, longitude: f64) -> Option<&'a T> {
+        let mut nearest = None;
+        search_nearest(&self.points, 0, &cartesian(latitude, longitude), &mut nearest);
+        nearest.map(|(point, _)| point)
+    }
+
+    /// Points within `radius` meters of the coordinates, closest first.
+    pub fn within_radius(&self, latitude: f64, longitude: f64, radius: f64) -> Vec<&'a T> {
+        let angle = (radius / EARTH_RADIUS).min(PI);
+        let chord = 2.0 * (angle / 2.0).sin();
+
+        let mut found = Vec::new();
+        search_within(&self.points, 0, &cartesian(latitude, longitude), chord * chord, &mut found);
+        found.sort_by(|(_, distance), (_, other_distance)| distance.partial_cmp(other_distance).unwrap_or(Ordering::Equal));
+        found.into_iter()
+            .map(|(point, _)| point)
+            .collect()
+    }
+}
+
+fn cartesian(latitude: f64, longitude: f64) -> Cartesian {
+    let (latitude, longitude) = (latitude.to_radians(), longitude.to_radians());
+    [latitude.cos() * longitude.cos(), latitude.cos() * longitude.sin(), latitude.sin()]
+}
+
+fn squared_distance(from: &Cartesian, to: &Cartesian) -> f64 {
+    from.iter()
+        .zip(to)
+        .map(|(from, to)| (from - to) * (from - to))
+        .sum()
+}
+
+/// Places the median of each axis in the middle of its slice, with the points before it
+/// on one side and the points after it on the other, cycling through the axes.
+fn arrange<T>(points: &mut [Located<T>], depth: usize) {
+    if points.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % 3;
+    let middle = points.len() / 2;
+    points.select_nth_unstable_by(middle, |(_, from), (_, to)| from[axis].partial_cmp(&to[axis]).unwrap_or(Ordering::Equal));
+
+    let (before, after) = points.split_at_mut(middle);
+    arrange(before, depth + 1);
+    arrange(&mut after[1..], depth + 1);
+}
+
+/// Slices of the points on the same side of the middle point as the target and on the other side,
+/// with the distance from the target to the plane splitting them.
+fn sides<'p, T>(points: &'p [Located<T>], depth: usize, target: &Cartesian) -> (&'p [Located<T>], &'p [Located<T>], f64) {
+    let middle = points.len() / 2;
+    let axis = depth % 3;
+    let delta = target[axis] - points[middle].1[axis];
+    if delta < 0.0 {
+        (&points[..middle], &points[middle + 1..], delta)
+    } else {
+        (&points[middle + 1..], &points[..middle], delta)
+    }
+}
+
+fn search_nearest<'a, T>(points: &[Located<&'a T>], depth: usize, target: &Cartesian, nearest: &mut Option<(&'a T, f64)>) {
+    if points.is_empty() {
+        return;
+    }
+
+    let (point, coordinates) = points[points.len() / 2];
+    let distance = squared_distance(&coordinates, target);
+    if nearest.is_none_or(|(_, nearest_distance)| distance < nearest_distance) {
+        *nearest = Some((point, distance));
+    }
+
+    let (near, far, delta) = sides(points, depth, target);
+    search_nearest(near, depth + 1, target, nearest);
+    if nearest.is_none_or(|(_, nearest_distance)| delta * delta < nearest_distance) {
+        search_nearest(far, depth + 1, target, nearest);
+    }
+}
+
+fn search_within<'a, T>(points: &[Located<&'a T>], depth: usize, target: &Cartesian, squared_radius: f64, found: &mut Vec<(&'a T, f64)>) {
+    if points.is_empty() {
+        return;
+    }
+
+    let (point, coordinates) = points[points.len() / 2];
+    let distance = squared_distance(&coordinates, target);
+    if distance <= squared_radius {
+        found.push((point, distance));
+    }
+
+    let (near, far, delta) = sides(points, depth, target);
+    search_within(near, depth + 1, target, squared_radius, found);
+    if delta * delta <= squared_radius {
+        search_within(far, depth + 1, target, squared_radius, found);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use node::Node;
+    use node::NodeBuilder;
+    use node::Point;
+    use spatial::*;
+
+    #[derive(Clone, PartialEq, Debug)]
+    struct Place {
+        name: String,
+        latitude: f64,
+        longitude: f64,
+    }
+
+    impl Point for Place {
+        type Identifier = String;
+
+        fn id(&self) -> String {
+            self.name.clone()
+        }
+    }
+
+    impl GeoPoint for Place {
+        fn latitude(&self) -> f64 {
+            self.latitude
+        }
+
+        fn longitude(&self) -> f64 {
+            self.longitude
+        }
+    }
+
+    // Given these cities connected in a line:
+    // Valencia - Madrid - Zaragoza - Barcelona
+    fn spain_net() -> Net<Place> {
+        Net {
+            nodes: vec![
+                node(place("Madrid", 40.4168, -3.7038), vec!["Valencia", "Zaragoza"]),
+                node(place("Zaragoza", 41.6488, -0.8891), vec!["Madrid", "Barcelona"]),
+                node(place("Valencia", 39.4699, -0.3763), vec!["Madrid"]),
+                node(place("Barcelona", 41.3874, 2.1686), vec!["Zaragoza"]),
+            ]
+        }
+    }
+
+    #[test]
+    fn nearest_should_snap_coordinates_to_the_closest_point() {
+        let net = spain_net();
+        let index = net.spatial_index();
+
+        let origin = index.nearest(40.45, -3.6).expect("should find a point near Madrid");
+        let destination = index.nearest(41.4, 2.0).expect("should find a point near Barcelona");
+        let paths: Vec<String> = net.find_paths(origin, destination)
+            .expect("should find paths between the snapped points")
+            .iter()
+            .map(|path| format!("{}", path))
+            .collect();
+
+        assert_eq!(paths, vec!["Madrid-Zaragoza-Barcelona"]);
+    }
+
+    #[test]
+    fn within_radius_should_return_the_points_closest_first() {
+        let net = spain_net();
+
+        let names: Vec<&str> = net.spatial_index().within_radius(40.4168, -3.7038, 320_000.0)
+            .iter()
+            .map(|place| &place.name[..])
+            .collect();
+
+        assert_eq!(names, vec!["Madrid", "Zaragoza", "Valencia"], "Barcelona is 505 km away");
+    }
+
+    #[test]
+    fn nearest_should_match_comparing_every_point() {
+        let places: Vec<Place> = (0..400)
+            .map(|index| place(&index.to_string(), (index / 20) as f64 * 0.37 - 3.0, (index % 20) as f64 * 0.53 + 179.0 - 10.0))
+            .collect();
+        let index = SpatialIndex::new(&places);
+
+        for &(latitude, longitude) in &[(0.0, 179.9), (-3.2, 170.1), (4.1, -179.5), (1.23, 175.55), (60.0, 0.0)] {
+            let expected = places.iter()
+                .min_by(|from, to| distance(from, latitude, longitude).partial_cmp(&distance(to, latitude, longitude)).unwrap())
+                .unwrap();
+
+            assert_eq!(index.nearest(latitude, longitude), Some(expected), "nearest to ({}, {})", latitude, longitude);
+        }
+        assert_eq!(SpatialIndex::<Place>::new(vec![]).nearest(0.0, 0.0), None);
+    }
+
+    fn distance(place: &Place, latitude: f64, longitude: f64) -> f64 {
+        ::geo::haversine_distance(place.latitude, place.longitude, latitude, longitude)
+    }
+
+    fn place(name: &str, latitude: f64, longitude: f64) -> Place {
+        Place { name: name.to_string(), latitude, longitude }
+    }
+
+    fn node(place: Place, connected_to: Vec<&str>) -> Node<Place> {
+        let mut builder = NodeBuilder::new();
+        builder.point(&place);
+        connected_to.iter()
+            .for_each(|name| {
+                builder.connected_point(&Place { name: name.to_string(), latitude: 0.0, longitude: 0.0 });
+            });
+        builder.build().unwrap()
+    }
+}
@@ -0,0 +1,246 @@
+use bidirectional;
+use compact::CompactNet;
+use index::NetIndex;
+use index::Topology;
+use net;
+use net::Net;
+use net::NetErrors;
+use node::Point;
+use observer::SearchControl;
+use observer::SearchObserver;
+use path::Path;
+use shortest_path;
+use std::slice;
+use std::time::Duration;
+use std::time::Instant;
+
+/// How much work a search did to get its results, for the searches that are observed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchStats {
+    nodes_expanded: usize,
+    branches_pruned: usize,
+    max_depth: usize,
+    paths_found: usize,
+    elapsed: Duration,
+}
+
+impl SearchStats {
+    pub fn nodes_expanded(&self) -> usize {
+        self.nodes_expanded
+    }
+
+    pub fn branches_pruned(&self) -> usize {
+        self.branches_pruned
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn paths_found(&self) -> usize {
+        self.paths_found
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl<T: Point, P> SearchObserver<T, P> for SearchStats {
+    fn on_expand(&mut self, _point: &T, depth: usize, _arrived_through: Option<&P>) -> SearchControl {
+        self.nodes_expanded += 1;
+        self.max_depth = self.max_depth.max(depth);
+        SearchControl::Continue
+    }
+
+    fn on_prune(&mut self, _from: &T, _to: &T, _payload: &P) -> SearchControl {
+        self.branches_pruned += 1;
+        SearchControl::Continue
+    }
+
+    fn on_path_found(&mut self, path: &Path<T>) -> SearchControl {
+        self.paths_found += 1;
+        self.max_depth = self.max_depth.max(path.hops());
+        SearchControl::Continue
+    }
+}
+
+impl<T: Point, P> Net<T, P> {
+    pub fn find_paths_with_stats(&self, origin: &T, destination: &T) -> (Result<Vec<Path<T>>, NetErrors>, SearchStats) {
+        find_paths_with_stats(&NetIndex::new(self), slice::from_ref(origin), slice::from_ref(destination))
+    }
+
+    pub fn find_paths_between_sets_with_stats(&self, origins: &[T], destinations: &[T]) -> (Result<Vec<Path<T>>, NetErrors>, SearchStats) {
+        find_paths_with_stats(&NetIndex::new(self), origins, destinations)
+    }
+
+    pub fn shortest_path_with_stats(&self, origin: &T, destination: &T) -> (Result<Path<T>, NetErrors>, SearchStats) {
+        shortest_path_with_stats(&NetIndex::new(self), slice::from_ref(origin), slice::from_ref(destination))
+    }
+
+    pub fn shortest_path_between_sets_with_stats(&self, origins: &[T], destinations: &[T]) -> (Result<Path<T>, NetErrors>, SearchStats) {
+        shortest_path_with_stats(&NetIndex::new(self), origins, destinations)
+    }
+
+    pub fn shortest_paths_per_pair_with_stats(&self, origins: &[T], destinations: &[T]) -> (Result<Vec<Path<T>>, NetErrors>, SearchStats) {
+        shortest_paths_per_pair_with_stats(&NetIndex::new(self), origins, destinations)
+    }
+
+    pub fn bidirectional_shortest_path_with_stats(&self, origin: &T, destination: &T) -> (Result<Path<T>, NetErrors>, SearchStats) {
+        bidirectional_shortest_path_with_stats(&NetIndex::new(self), origin, destination)
+    }
+}
+
+impl<T: Point, P> CompactNet<T, P> {
+    pub fn find_paths_with_stats(&self, origin: &T, destination: &T) -> (Result<Vec<Path<T>>, NetErrors>, SearchStats) {
+        find_paths_with_stats(self, slice::from_ref(origin), slice::from_ref(destination))
+    }
+
+    pub fn find_paths_between_sets_with_stats(&self, origins: &[T], destinations: &[T]) -> (Result<Vec<Path<T>>, NetErrors>, SearchStats) {
+        find_paths_with_stats(self, origins, destinations)
+    }
+
+    pub fn shortest_path_with_stats(&self, origin: &T, destination: &T) -> (Result<Path<T>, NetErrors>, SearchStats) {
+        shortest_path_with_stats(self, slice::from_ref(origin), slice::from_ref(destination))
+    }
+
+    pub fn shortest_path_between_sets_with_stats(&self, origins: &[T], destinations: &[T]) -> (Result<Path<T>, NetErrors>, SearchStats) {
+        shortest_path_with_stats(self, origins, destinations)
+    }
+
+    pub fn shortest_paths_per_pair_with_stats(&self, origins: &[T], destinations: &[T]) -> (Result<Vec<Path<T>>, NetErrors>, SearchStats) {
+        shortest_paths_per_pair_with_stats(self, origins, destinations)
+    }
+
+    pub fn bidirectional_shortest_path_with_stats(&self, origin: &T, destination: &T) -> (Result<Path<T>, NetErrors>, SearchStats) {
+        bidirectional_shortest_path_with_stats(self, origin, destination)
+    }
+}
+
+fn find_paths_with_stats<T: Point, G: Topology<T>>(graph: &G, origins: &[T], destinations: &[T]) -> (Result<Vec<Path<T>>, NetErrors>, SearchStats) {
+    with_stats(|stats| net::find_paths_between_sets(graph, origins, destinations, stats))
+}
+
+fn shortest_path_with_stats<T: Point, G: Topology<T>>(graph: &G, origins: &[T], destinations: &[T]) -> (Result<Path<T>, NetErrors>, SearchStats) {
+    with_stats(|stats| shortest_path::shortest_path_between_sets(graph, origins, destinations, stats))
+}
+
+fn shortest_paths_per_pair_with_stats<T: Point, G: Topology<T>>(graph: &G, origins: &[T], destinations: &[T]) -> (Result<Vec<Path<T>>, NetErrors>, SearchStats) {
+    with_stats(|stats| shortest_path::shortest_paths_per_pair(graph, origins, destinations, stats))
+}
+
+fn bidirectional_shortest_path_with_stats<T: Point, G: Topology<T>>(graph: &G, origin: &T, destination: &T) -> (Result<Path<T>, NetErrors>, SearchStats) {
+    with_stats(|stats| bidirectional::bidirectional_shortest_path(graph, origin, destination, stats))
+}
+
+/// Runs a search counting its work through the observer hooks, and timing it.
+fn with_stats<R, F>(search: F) -> (R, SearchStats)
+    where F: FnOnce(&mut SearchStats) -> R {
+    let mut stats = SearchStats::default();
+    let started = Instant::now();
+    let result = search(&mut stats);
+    stats.elapsed = started.elapsed();
+
+    (result, stats)
+}
+
+#[cfg(test)]
+mod test {
+    use net::Net;
+    use node::Node;
+    use node::NodeBuilder;
+    use stats::*;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this net of points:
+    // A - B - C
+    //  \  |  /
+    //   \ | /
+    //     D
+    fn diamond_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', "BD"),
+                node('B', "ACD"),
+                node('C', "BD"),
+                node('D', "ACB"),
+            ]
+        }
+    }
+
+    #[test]
+    fn find_paths_with_stats_should_count_the_work_done() {
+        let net = diamond_net();
+
+        let (paths, stats) = net.find_paths_with_stats(&simple_point('A'), &simple_point('C'));
+
+        assert_eq!(paths.expect("should find paths from A to C").len(), 4);
+        assert_eq!(stats.paths_found(), 4);
+        assert_eq!(stats.nodes_expanded(), 5, "should expand A, B, A-B-D, D and A-D-B");
+        assert_eq!(stats.branches_pruned(), 6);
+        assert_eq!(stats.max_depth(), 3);
+    }
+
+    #[test]
+    fn shortest_path_with_stats_should_return_stats_even_without_path() {
+        let mut net = diamond_net();
+        net.nodes.push(node('E', ""));
+
+        let (path, stats) = net.shortest_path_with_stats(&simple_point('A'), &simple_point('E'));
+
+        assert!(path.is_err(), "E should not be reachable");
+        assert_eq!(stats.nodes_expanded(), 4);
+        assert_eq!(stats.paths_found(), 0);
+    }
+
+    #[test]
+    fn bidirectional_shortest_path_with_stats_should_count_the_path_found() {
+        let net = diamond_net();
+
+        let (path, stats) = net.bidirectional_shortest_path_with_stats(&simple_point('A'), &simple_point('C'));
+
+        assert_eq!(path.expect("should find a path from A to C").hops(), 2);
+        assert_eq!(stats.paths_found(), 1);
+        assert_eq!(stats.nodes_expanded(), 4, "should expand A and C, and then B and D from A");
+        assert_eq!(stats.branches_pruned(), 3, "B and D back to A, and D to B");
+        assert_eq!(stats.max_depth(), 2);
+    }
+
+    #[test]
+    fn shortest_paths_per_pair_with_stats_should_count_the_work_done_from_every_origin() {
+        let net = diamond_net();
+
+        let (paths, stats) = net.shortest_paths_per_pair_with_stats(&[simple_point('A'), simple_point('C')], &[simple_point('B'), simple_point('D')]);
+
+        assert_eq!(paths.expect("should find paths from A and C to B and D").len(), 4);
+        assert_eq!(stats.paths_found(), 4);
+        assert_eq!(stats.nodes_expanded(), 8, "should expand the whole net from each origin");
+        assert_eq!(stats.branches_pruned(), 10, "each of the 5 connections is followed once and pruned back from each origin");
+        assert_eq!(stats.max_depth(), 2);
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connected_to: &str) -> Node<SimplePoint> {
+        let connected_points: Vec<SimplePoint> = connected_to.chars().map(simple_point).collect();
+        NodeBuilder::new()
+            .point(&simple_point(name))
+            .connected_points(&connected_points)
+            .build()
+            .unwrap()
+    }
+}
@@ -0,0 +1,331 @@
+use compact::CompactNet;
+use index::NetIndex;
+use index::Topology;
+use net::Net;
+use net::NetErrors;
+use node::Point;
+use path::Path;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub type Time = u64;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Departure {
+    pub time: Time,
+    pub duration: Time,
+}
+
+/// Departures of a connection, each one taking its own time to reach the connected point.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Timetable {
+    departures: Vec<Departure>,
+}
+
+impl Timetable {
+    pub fn new() -> Timetable {
+        Timetable::default()
+    }
+
+    pub fn departure(&mut self, time: Time, duration: Time) -> &mut Self {
+        let index = self.departures.partition_point(|departure| departure.time <= time);
+        self.departures.insert(index, Departure { time, duration });
+
+        self
+    }
+
+    pub fn departures(&self) -> &[Departure] {
+        &self.departures
+    }
+
+    pub fn shortest_duration(&self) -> Option<Time> {
+        self.departures.iter()
+            .map(|departure| departure.duration)
+            .min()
+    }
+
+    /// Departure that arrives first among the ones leaving at `ready_time` or later, as
+    /// its departure and arrival times. A later departure may arrive before an earlier one.
+    pub fn earliest_arrival(&self, ready_time: Time) -> Option<(Time, Time)> {
+        let first_available = self.departures.partition_point(|departure| departure.time < ready_time);
+
+        let mut earliest: Option<(Time, Time)> = None;
+        for departure in &self.departures[first_available..] {
+            if earliest.is_some_and(|(_, arrival)| departure.time >= arrival) {
+                break;
+            }
+            let arrival = departure.time + departure.duration;
+            if earliest.is_none_or(|(_, earliest_arrival)| arrival < earliest_arrival) {
+                earliest = Some((departure.time, arrival));
+            }
+        }
+
+        earliest
+    }
+}
+
+/// Times at a point of a timed path. The last point of the path has no departure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointTimes {
+    pub arrival: Time,
+    pub departure: Option<Time>,
+}
+
+#[derive(Debug)]
+pub struct TimedPath<T: Point> {
+    path: Path<T>,
+    times: Vec<PointTimes>,
+}
+
+impl<T: Point> TimedPath<T> {
+    pub fn path(&self) -> &Path<T> {
+        &self.path
+    }
+
+    /// Times at each point of the path, in the same order as its points.
+    pub fn times(&self) -> &[PointTimes] {
+        &self.times
+    }
+
+    pub fn arrival(&self) -> Time {
+        self.times.last().map(|times| times.arrival).unwrap_or_default()
+    }
+}
+
+impl<T: Point, P> Net<T, P> {
+    /// Finds the path arriving first to the destination leaving the origin at `departure_time`,
+    /// waiting at each point for the departures of its timetables. Connections without
+    /// timetable can be taken at any time and take their cost to be travelled.
+    pub fn earliest_arrival(&self, origin: &T, destination: &T, departure_time: Time) -> Result<TimedPath<T>, NetErrors> {
+        earliest_arrival(&NetIndex::new(self), origin, destination, departure_time)
+    }
+}
+
+impl<T: Point, P> CompactNet<T, P> {
+    pub fn earliest_arrival(&self, origin: &T, destination: &T, departure_time: Time) -> Result<TimedPath<T>, NetErrors> {
+        earliest_arrival(self, origin, destination, departure_time)
+    }
+}
+
+/// Where a point was reached from: the previous position, the connection taken from it and its departure time.
+type Arrival = (usize, usize, Time);
+
+fn earliest_arrival<T, G>(graph: &G, origin: &T, destination: &T, departure_time: Time) -> Result<TimedPath<T>, NetErrors>
+    where T: Point, G: Topology<T> {
+    let origin = graph.position(origin)?;
+    let destination = graph.position(destination)?;
+
+    let mut arrivals: Vec<Option<Time>> = vec![None; graph.len()];
+    let mut previous: Vec<Option<Arrival>> = vec![None; graph.len()];
+    let mut settled = vec![false; graph.len()];
+    let mut pending = BinaryHeap::new();
+    arrivals[origin] = Some(departure_time);
+    pending.push(Reverse((departure_time, origin)));
+
+    while let Some(Reverse((arrival, current))) = pending.pop() {
+        if settled[current] {
+            continue;
+        }
+        settled[current] = true;
+
+        if current == destination {
+            return Ok(timed_path(graph, &arrivals, &previous, destination));
+        }
+
+        for (connection, &(next, cost)) in graph.connections(current).iter().enumerate() {
+            let departure_and_arrival = match graph.timetable(current, connection) {
+                Some(timetable) => timetable.earliest_arrival(arrival),
+                None => Some((arrival, arrival + cost))
+            };
+            if let Some((departure, next_arrival)) = departure_and_arrival {
+                if !settled[next] && arrivals[next].is_none_or(|known_arrival| next_arrival < known_arrival) {
+                    arrivals[next] = Some(next_arrival);
+                    previous[next] = Some((current, connection, departure));
+                    pending.push(Reverse((next_arrival, next)));
+                }
+            }
+        }
+    }
+
+    Err(NetErrors::NoPathFound)
+}
+
+fn timed_path<T: Point, G: Topology<T>>(graph: &G, arrivals: &[Option<Time>], previous: &[Option<Arrival>], destination: usize) -> TimedPath<T> {
+    let mut positions = vec![destination];
+    let mut connections = Vec::new();
+    let mut departures = vec![None];
+    let mut current = destination;
+    while let Some((previous_position, connection, departure)) = previous[current] {
+        positions.push(previous_position);
+        connections.push(connection);
+        departures.push(Some(departure));
+        current = previous_position;
+    }
+    positions.reverse();
+    connections.reverse();
+    departures.reverse();
+
+    let times = positions.iter()
+        .zip(departures)
+        .map(|(position, departure)| PointTimes {
+            arrival: arrivals[*position].expect("points of the path are reached"),
+            departure,
+        })
+        .collect();
+    let path = graph.path_through(&positions, &connections);
+
+    TimedPath { path, times }
+}
+
+#[cfg(test)]
+mod test {
+    use node::ConnectionBuilder;
+    use node::Node;
+    use node::NodeBuilder;
+    use timetable::*;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this transit net of points, where A-B and B-C have timetables
+    // and C is connected to D by a walk of 5:
+    // A ==> B ==> C -5-> D
+    // \==========^
+    fn transit_net() -> Net<SimplePoint> {
+        let mut a_to_b = Timetable::new();
+        a_to_b.departure(10, 10).departure(20, 10);
+        let mut b_to_c = Timetable::new();
+        b_to_c.departure(25, 5).departure(35, 5);
+        let mut a_to_c = Timetable::new();
+        a_to_c.departure(12, 30);
+
+        Net {
+            nodes: vec![
+                NodeBuilder::new()
+                    .point(&simple_point('A'))
+                    .connected_point_with_timetable(&simple_point('B'), &a_to_b)
+                    .connected_point_with_timetable(&simple_point('C'), &a_to_c)
+                    .build()
+                    .unwrap(),
+                NodeBuilder::new()
+                    .point(&simple_point('B'))
+                    .connected_point_with_timetable(&simple_point('C'), &b_to_c)
+                    .build()
+                    .unwrap(),
+                NodeBuilder::new()
+                    .point(&simple_point('C'))
+                    .connected_point_with_cost(&simple_point('D'), 5)
+                    .build()
+                    .unwrap(),
+                node('D'),
+            ]
+        }
+    }
+
+    #[test]
+    fn timetable_should_pick_the_departure_arriving_first() {
+        let mut timetable = Timetable::new();
+        timetable.departure(20, 5).departure(10, 30).departure(40, 1);
+
+        assert_eq!(timetable.earliest_arrival(5), Some((20, 25)), "leaving at 20 arrives before leaving at 10");
+        assert_eq!(timetable.earliest_arrival(21), Some((40, 41)));
+        assert_eq!(timetable.earliest_arrival(41), None);
+        assert_eq!(timetable.shortest_duration(), Some(1));
+    }
+
+    #[test]
+    fn earliest_arrival_should_wait_for_the_departures() {
+        let net = transit_net();
+
+        let timed_path = net.earliest_arrival(&simple_point('A'), &simple_point('D'), 0)
+            .expect("should reach D from A");
+
+        assert_eq!(format!("{}", timed_path.path()), "A-B-C-D");
+        assert_eq!(timed_path.times(), &[
+            PointTimes { arrival: 0, departure: Some(10) },
+            PointTimes { arrival: 20, departure: Some(25) },
+            PointTimes { arrival: 30, departure: Some(30) },
+            PointTimes { arrival: 35, departure: None },
+        ]);
+        assert_eq!(timed_path.arrival(), 35);
+    }
+
+    #[test]
+    fn earliest_arrival_should_prefer_a_later_departure_arriving_first() {
+        let net = transit_net();
+
+        let timed_path = net.earliest_arrival(&simple_point('A'), &simple_point('C'), 11)
+            .expect("should reach C from A");
+
+        assert_eq!(format!("{}", timed_path.path()), "A-B-C", "B-C leaving at 35 arrives at 40, before A-C at 42");
+        assert_eq!(timed_path.arrival(), 40);
+    }
+
+    #[test]
+    fn earliest_arrival_should_throw_after_the_last_departure() {
+        let net = transit_net();
+
+        match net.earliest_arrival(&simple_point('A'), &simple_point('D'), 21) {
+            Err(NetErrors::NoPathFound) => {},
+            other => panic!("NoPathFound expected, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn earliest_arrival_should_wait_for_the_departures_on_a_compact_net() {
+        let compact_net = transit_net().compact();
+
+        let timed_path = compact_net.earliest_arrival(&simple_point('A'), &simple_point('D'), 0)
+            .expect("should reach D from A");
+
+        assert_eq!(format!("{}", timed_path.path()), "A-B-C-D");
+        assert_eq!(timed_path.arrival(), 35);
+    }
+
+    #[test]
+    fn earliest_arrival_should_use_the_timetable_of_each_labeled_connection() {
+        let (a, b) = (simple_point('A'), simple_point('B'));
+        let mut bus = Timetable::new();
+        bus.departure(20, 10);
+        let mut train = Timetable::new();
+        train.departure(15, 3);
+        let net = Net {
+            nodes: vec![
+                NodeBuilder::new()
+                    .point(&a)
+                    .connected_point_through(&b, ConnectionBuilder::new(10).label("bus").timetable(&bus))
+                    .connected_point_through(&b, ConnectionBuilder::new(3).label("train").timetable(&train))
+                    .build()
+                    .unwrap(),
+                node('B'),
+            ]
+        };
+
+        let by_train = net.earliest_arrival(&a, &b, 0).expect("should reach B from A");
+        let by_bus = net.earliest_arrival(&a, &b, 16).expect("should reach B from A");
+
+        assert_eq!((by_train.path().labels(), by_train.arrival()), (&[Some(String::from("train"))][..], 18));
+        assert_eq!((by_bus.path().labels(), by_bus.arrival()), (&[Some(String::from("bus"))][..], 30));
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char) -> Node<SimplePoint> {
+        NodeBuilder::new()
+            .point(&simple_point(name))
+            .build()
+            .unwrap()
+    }
+}
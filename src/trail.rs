@@ -0,0 +1,130 @@
+use index::Topology;
+use node::Point;
+use path::Path;
+use std::iter;
+use std::rc::Rc;
+
+/// Path being explored by a search, kept as a persistent list of positions from its
+/// last point backwards. Extending a trail shares every position already in it instead
+/// of copying them, and points are only cloned when the trail becomes a `Path`.
+pub struct Trail {
+    position: usize,
+    connection: usize,
+    previous: Option<Rc<Trail>>,
+    hops: usize,
+}
+
+impl Trail {
+    pub fn start(position: usize) -> Rc<Trail> {
+        Rc::new(Trail { position, connection: 0, previous: None, hops: 0 })
+    }
+
+    /// Extends the trail to `position` through the connection with index `connection` among
+    /// the connections of its last position.
+    pub fn extend(trail: &Rc<Trail>, position: usize, connection: usize) -> Rc<Trail> {
+        Rc::new(Trail { position, connection, previous: Some(Rc::clone(trail)), hops: trail.hops + 1 })
+    }
+
+    pub fn last_position(&self) -> usize {
+        self.position
+    }
+
+    pub fn previous_position(&self) -> Option<usize> {
+        self.previous.as_ref().map(|previous| previous.position)
+    }
+
+    /// Position the trail comes from and the index of the connection it took to its last position.
+    pub fn arrived_through(&self) -> Option<(usize, usize)> {
+        self.previous.as_ref().map(|previous| (previous.position, self.connection))
+    }
+
+    pub fn hops(&self) -> usize {
+        self.hops
+    }
+
+    pub fn contains(&self, position_to_check: usize) -> bool {
+        self.positions().any(|position| position == position_to_check)
+    }
+
+    pub fn to_path<T: Point, G: Topology<T>>(&self, graph: &G) -> Path<T> {
+        graph.path_through(&self.positions_from_start(), &self.connections_from_start())
+    }
+
+    /// Indexes of the connections taken from each position of the trail but the last one.
+    pub fn connections_from_start(&self) -> Vec<usize> {
+        let mut connections = Vec::with_capacity(self.hops);
+        let mut current = self;
+        while let Some(ref previous) = current.previous {
+            connections.push(current.connection);
+            current = previous;
+        }
+        connections.reverse();
+        connections
+    }
+
+    pub fn positions_from_start(&self) -> Vec<usize> {
+        let mut positions: Vec<usize> = self.positions().collect();
+        positions.reverse();
+        positions
+    }
+
+    /// Positions of the trail from the last one to the first one.
+    fn positions(&self) -> impl Iterator<Item=usize> + '_ {
+        let mut current = Some(self);
+        iter::from_fn(move || {
+            let trail = current?;
+            current = trail.previous.as_deref();
+            Some(trail.position)
+        })
+    }
+}
+
+impl Drop for Trail {
+    // Unlinks the trails no one else shares one by one, instead of dropping them recursively
+    fn drop(&mut self) {
+        let mut previous = self.previous.take();
+        while let Some(trail) = previous {
+            previous = match Rc::try_unwrap(trail) {
+                Ok(mut unshared_trail) => unshared_trail.previous.take(),
+                Err(_) => None
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use trail::*;
+
+    #[test]
+    fn trails_extended_from_the_same_trail_should_share_its_positions() {
+        let shared = Trail::extend(&Trail::start(1), 2, 0);
+        let one_way = Trail::extend(&shared, 3, 0);
+        let other_way = Trail::extend(&shared, 4, 1);
+
+        assert_eq!(Rc::strong_count(&shared), 3, "both trails should point to the shared one");
+        assert_eq!(one_way.positions().collect::<Vec<usize>>(), vec![3, 2, 1]);
+        assert_eq!(other_way.positions().collect::<Vec<usize>>(), vec![4, 2, 1]);
+        assert_eq!(other_way.hops(), 2);
+        assert_eq!(other_way.connections_from_start(), vec![0, 1]);
+    }
+
+    #[test]
+    fn dropping_a_long_trail_should_not_overflow_the_stack() {
+        let mut trail = Trail::start(0);
+        for position in 1..1_000_001 {
+            trail = Trail::extend(&trail, position, 0);
+        }
+
+        assert_eq!(trail.hops(), 1_000_000);
+    }
+
+    #[test]
+    fn trail_should_know_the_positions_it_contains() {
+        let trail = Trail::extend(&Trail::start(1), 2, 0);
+
+        assert!(trail.contains(1));
+        assert!(trail.contains(2));
+        assert!(!trail.contains(3));
+    }
+}
@@ -0,0 +1,302 @@
+use compact::CompactNet;
+use index::NetIndex;
+use index::Topology;
+use net;
+use net::Net;
+use net::NetErrors;
+use node::Cost;
+use node::Point;
+use observer::NoObserver;
+use path::Path;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::slice;
+use trail::Trail;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Turn {
+    Forbidden,
+    Cost(Cost),
+}
+
+/// Ids of the point a turn comes from, the point it turns at and the point it goes to.
+type TurnKey<I> = (I, I, I);
+
+/// Cost of turning from one connection into the next one, or whether that turn is forbidden.
+#[derive(Debug)]
+pub struct TurnTable<T: Point> {
+    turns: HashMap<TurnKey<T::Identifier>, Turn>,
+}
+
+impl<T: Point> Default for TurnTable<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Point> TurnTable<T> {
+    pub fn new() -> TurnTable<T> {
+        TurnTable { turns: HashMap::new() }
+    }
+
+    pub fn forbid(&mut self, from: &T, via: &T, to: &T) -> &mut Self {
+        self.turns.insert((from.id(), via.id(), to.id()), Turn::Forbidden);
+
+        self
+    }
+
+    pub fn cost(&mut self, from: &T, via: &T, to: &T, cost: Cost) -> &mut Self {
+        self.turns.insert((from.id(), via.id(), to.id()), Turn::Cost(cost));
+
+        self
+    }
+
+    pub fn turn(&self, from: &T, via: &T, to: &T) -> Option<Turn> {
+        self.turns.get(&(from.id(), via.id(), to.id())).cloned()
+    }
+
+    /// Turns between points of the graph, by their positions.
+    fn positions<G: Topology<T>>(&self, graph: &G) -> HashMap<TurnKey<usize>, Turn> {
+        self.turns.iter()
+            .filter_map(|((from, via, to), turn)| {
+                let positions = (graph.position_of(from)?, graph.position_of(via)?, graph.position_of(to)?);
+                Some((positions, *turn))
+            })
+            .collect()
+    }
+}
+
+impl<T: Point, P> Net<T, P> {
+    /// Finds all the paths that do not take any forbidden turn.
+    pub fn find_paths_with_turns(&self, origin: &T, destination: &T, turns: &TurnTable<T>) -> Result<Vec<Path<T>>, NetErrors> {
+        find_paths_with_turns(&NetIndex::new(self), origin, destination, turns)
+    }
+
+    /// Finds the cheapest path adding the cost of its turns and not taking any forbidden one.
+    /// Paths never go through a point twice, so it fails with `NoPathFound` when the only way
+    /// to avoid a forbidden turn is a loop.
+    pub fn shortest_path_with_turns(&self, origin: &T, destination: &T, turns: &TurnTable<T>) -> Result<Path<T>, NetErrors> {
+        shortest_path_with_turns(&NetIndex::new(self), origin, destination, turns)
+    }
+}
+
+impl<T: Point, P> CompactNet<T, P> {
+    pub fn find_paths_with_turns(&self, origin: &T, destination: &T, turns: &TurnTable<T>) -> Result<Vec<Path<T>>, NetErrors> {
+        find_paths_with_turns(self, origin, destination, turns)
+    }
+
+    pub fn shortest_path_with_turns(&self, origin: &T, destination: &T, turns: &TurnTable<T>) -> Result<Path<T>, NetErrors> {
+        shortest_path_with_turns(self, origin, destination, turns)
+    }
+}
+
+fn find_paths_with_turns<T: Point, G: Topology<T>>(graph: &G, origin: &T, destination: &T, turns: &TurnTable<T>) -> Result<Vec<Path<T>>, NetErrors> {
+    let origin = graph.position(origin)?;
+    let destinations = graph.positions(slice::from_ref(destination))?;
+    let turns = turns.positions(graph);
+
+    let can_follow = |trail: &Trail, next: usize| match trail.previous_position() {
+        Some(from) => turns.get(&(from, trail.last_position(), next)) != Some(&Turn::Forbidden),
+        None => true
+    };
+
+    let mut pending_trails: Vec<Rc<Trail>> = vec![Trail::start(origin)];
+    let mut paths = Vec::new();
+    net::follow_trails_to_destinations(graph, &destinations, &mut pending_trails, &mut paths, usize::MAX, &can_follow, &mut NoObserver);
+
+    if paths.is_empty() {
+        Err(NetErrors::NoPathFound)
+    } else {
+        Ok(paths)
+    }
+}
+
+/// Partial route of the turn search, with the sorted positions it went through, so it
+/// is never extended to any of them again.
+struct TurnLabel {
+    position: usize,
+    visited: Vec<usize>,
+    previous: Option<(usize, usize)>,
+}
+
+/// Label setting search over the points together with the point they were reached from, so turns
+/// can be checked when leaving them. A label is skipped when an expanded one ended the same way
+/// through no other points, as it can only be followed by the routes that one is followed by.
+fn shortest_path_with_turns<T: Point, G: Topology<T>>(graph: &G, origin: &T, destination: &T, turns: &TurnTable<T>) -> Result<Path<T>, NetErrors> {
+    let origin = graph.position(origin)?;
+    let destination = graph.position(destination)?;
+    let turns = turns.positions(graph);
+
+    let mut labels = vec![TurnLabel { position: origin, visited: vec![origin], previous: None }];
+    let mut expanded_labels: HashMap<(usize, Option<usize>), Vec<usize>> = HashMap::new();
+    let mut pending = BinaryHeap::new();
+    pending.push(Reverse((0, 0)));
+
+    while let Some(Reverse((cost, label_index))) = pending.pop() {
+        let label = &labels[label_index];
+        let from = label.previous.map(|(previous_label, _)| labels[previous_label].position);
+        let expanded_through = expanded_labels.entry((label.position, from)).or_default();
+        if expanded_through.iter().any(|&expanded| is_subset(&labels[expanded].visited, &label.visited)) {
+            continue;
+        }
+        if label.position == destination {
+            return Ok(turn_labels_path(graph, &labels, label_index));
+        }
+        expanded_through.push(label_index);
+
+        let mut next_labels = Vec::new();
+        for (connection, &(next, connection_cost)) in graph.connections(label.position).iter().enumerate() {
+            let unvisited = match label.visited.binary_search(&next) {
+                Ok(_) => continue,
+                Err(unvisited) => unvisited
+            };
+            let turn_cost = match from.and_then(|from| turns.get(&(from, label.position, next))) {
+                Some(Turn::Forbidden) => continue,
+                Some(Turn::Cost(turn_cost)) => *turn_cost,
+                None => 0
+            };
+
+            let mut visited = label.visited.clone();
+            visited.insert(unvisited, next);
+            next_labels.push((cost + connection_cost + turn_cost, TurnLabel { position: next, visited, previous: Some((label_index, connection)) }));
+        }
+
+        for (next_cost, next_label) in next_labels {
+            pending.push(Reverse((next_cost, labels.len())));
+            labels.push(next_label);
+        }
+    }
+
+    Err(NetErrors::NoPathFound)
+}
+
+/// Whether every position of the first sorted list is in the second one.
+fn is_subset(positions: &[usize], other_positions: &[usize]) -> bool {
+    positions.iter().all(|position| other_positions.binary_search(position).is_ok())
+}
+
+fn turn_labels_path<T: Point, G: Topology<T>>(graph: &G, labels: &[TurnLabel], last_label: usize) -> Path<T> {
+    let mut positions = vec![labels[last_label].position];
+    let mut connections = Vec::new();
+    let mut current = last_label;
+    while let Some((previous_label, connection)) = labels[current].previous {
+        positions.push(labels[previous_label].position);
+        connections.push(connection);
+        current = previous_label;
+    }
+    positions.reverse();
+    connections.reverse();
+
+    graph.path_through(&positions, &connections)
+}
+
+#[cfg(test)]
+mod test {
+    use node::Node;
+    use node::NodeBuilder;
+    use turns::*;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct SimplePoint {
+        name: char
+    }
+
+    impl Point for SimplePoint {
+        type Identifier = char;
+
+        fn id(&self) -> char {
+            self.name
+        }
+    }
+
+    // Given this net of streets:
+    // A -1- B -1- C
+    //       |     |
+    //       1     1
+    //       |     |
+    //       D -1- E
+    fn streets_net() -> Net<SimplePoint> {
+        Net {
+            nodes: vec![
+                node('A', vec![('B', 1)]),
+                node('B', vec![('A', 1), ('C', 1), ('D', 1)]),
+                node('C', vec![('B', 1), ('E', 1)]),
+                node('D', vec![('B', 1), ('E', 1)]),
+                node('E', vec![('C', 1), ('D', 1)]),
+            ]
+        }
+    }
+
+    #[test]
+    fn find_paths_should_not_take_forbidden_turns() {
+        let net = streets_net();
+        let mut turns = TurnTable::new();
+        turns.forbid(&simple_point('A'), &simple_point('B'), &simple_point('D'));
+
+        let paths: Vec<String> = net.find_paths_with_turns(&simple_point('A'), &simple_point('D'), &turns)
+            .expect("should find paths from A to D")
+            .iter()
+            .map(|path| format!("{}", path))
+            .collect();
+
+        assert_eq!(paths, vec!["A-B-C-E-D"]);
+    }
+
+    #[test]
+    fn shortest_path_should_add_the_cost_of_turns() {
+        let net = streets_net();
+        let mut turns = TurnTable::new();
+        turns.cost(&simple_point('A'), &simple_point('B'), &simple_point('D'), 5)
+            .forbid(&simple_point('B'), &simple_point('C'), &simple_point('B'));
+
+        let path = net.shortest_path_with_turns(&simple_point('A'), &simple_point('D'), &turns)
+            .expect("should find a path from A to D");
+
+        assert_eq!(format!("{}", path), "A-B-C-E-D", "turning at B costs more than going around");
+    }
+
+    #[test]
+    fn shortest_path_should_go_around_a_forbidden_turn_without_loops() {
+        let mut net = streets_net();
+        net.nodes[2] = node('C', vec![('B', 1), ('E', 10)]);
+        net.nodes[4] = node('E', vec![('C', 10), ('D', 1)]);
+        let mut turns = TurnTable::new();
+        turns.forbid(&simple_point('D'), &simple_point('B'), &simple_point('A'));
+
+        let path = net.shortest_path_with_turns(&simple_point('E'), &simple_point('A'), &turns)
+            .expect("should find a path from E to A");
+
+        assert_eq!(format!("{}", path), "E-C-B-A", "turning around at C costs less but goes through B twice");
+        assert_eq!(net.validate_path(&path).expect("should be a valid path"), 12);
+    }
+
+    #[test]
+    fn shortest_path_should_throw_if_the_only_legal_route_loops() {
+        let mut net = streets_net();
+        net.nodes[2] = node('C', vec![('B', 1)]);
+        net.nodes[4] = node('E', vec![('D', 1)]);
+        let mut turns = TurnTable::new();
+        turns.forbid(&simple_point('D'), &simple_point('B'), &simple_point('A'));
+
+        match net.shortest_path_with_turns(&simple_point('E'), &simple_point('A'), &turns) {
+            Err(NetErrors::NoPathFound) => {},
+            other => panic!("NoPathFound expected as E-D-B-C-B-A goes through B twice, got {:?}", other)
+        }
+    }
+
+    fn simple_point(name: char) -> SimplePoint {
+        SimplePoint { name }
+    }
+
+    fn node(name: char, connections: Vec<(char, Cost)>) -> Node<SimplePoint> {
+        let mut builder = NodeBuilder::new();
+        builder.point(&simple_point(name));
+        connections.into_iter()
+            .for_each(|(to, cost)| {
+                builder.connected_point_with_cost(&simple_point(to), cost);
+            });
+        builder.build().unwrap()
+    }
+}